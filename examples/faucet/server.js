@@ -40,21 +40,13 @@ fastify.post('/send-money/:destinationAddress', async (request, reply) => {
   try {
     const {
       OutputPolicy,
-      TransactionBuilder,
+      SimpleTransactionBuilder,
       Address,
-      Input,
       Value,
       Fee,
-      Fragment,
       PrivateKey,
-      Witness,
       SpendingCounter,
       Hash,
-      Account,
-      InputOutputBuilder,
-      PayloadAuthData,
-      Payload,
-      Witnesses,
       // eslint-disable-next-line camelcase
       uint8array_to_hex
     } = await chainLibs;
@@ -66,10 +58,7 @@ fastify.post('/send-money/:destinationAddress', async (request, reply) => {
       fastify.config.JORMUNGANDR_API
     );
 
-    const iobuilder = InputOutputBuilder.empty();
-
     const secretKey = PrivateKey.from_bech32(fastify.config.SECRET_KEY);
-    const faucetAccount = Account.single_from_public_key(secretKey.to_public());
 
     // Fee (#inputs + #outputs) * coefficient + constant + #certificates*certificate
     // #inputs = 1; #outputs = 2; #certificates = 0
@@ -92,50 +81,28 @@ fastify.post('/send-money/:destinationAddress', async (request, reply) => {
       return;
     }
 
-    const input = Input.from_account(
-      faucetAccount,
-      Value.from_str(inputAmount.toString())
+    const builder = SimpleTransactionBuilder.with_parameters(
+      Hash.from_hex(nodeSettings.block0Hash),
+      Fee.linear_fee(
+        Value.from_str(nodeSettings.fees.constant.toString()),
+        Value.from_str(nodeSettings.fees.coefficient.toString()),
+        Value.from_str(nodeSettings.fees.certificate.toString())
+      )
     );
 
-    iobuilder.add_input(input);
+    builder.add_account_input(
+      secretKey,
+      Value.from_str(inputAmount.toString()),
+      SpendingCounter.from_u32(accountStatus.counter)
+    );
 
-    iobuilder.add_output(
+    builder.add_output(
       Address.from_string(request.params.destinationAddress),
       Value.from_str(fastify.config.LOVELACES_TO_GIVE.toString())
     );
 
-    const feeAlgorithm = Fee.linear_fee(
-      Value.from_str(nodeSettings.fees.constant.toString()),
-      Value.from_str(nodeSettings.fees.coefficient.toString()),
-      Value.from_str(nodeSettings.fees.certificate.toString())
-    );
-
     // The amount is exact, that's why we use `forget()`
-    const IOs = iobuilder.seal_with_output_policy(
-      Payload.no_payload(),
-      feeAlgorithm,
-      OutputPolicy.forget()
-    );
-
-    const builderSetWitness = new TransactionBuilder()
-      .no_payload()
-      .set_ios(IOs.inputs(), IOs.outputs());
-
-    const witness = Witness.for_account(
-      Hash.from_hex(nodeSettings.block0Hash),
-      builderSetWitness.get_auth_data_for_witness(),
-      secretKey,
-      SpendingCounter.from_u32(accountStatus.counter)
-    );
-
-    const witnesses = Witnesses.new();
-    witnesses.add(witness);
-
-    const signedTx = builderSetWitness
-      .set_witnesses(witnesses)
-      .set_payload_auth(PayloadAuthData.for_no_payload());
-
-    const message = Fragment.from_transaction(signedTx);
+    const message = builder.finalize(OutputPolicy.forget());
 
     // Send the transaction
     await jormungandrApi.postMsg(
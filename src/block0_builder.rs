@@ -0,0 +1,244 @@
+//! Construction of genesis blocks
+//!
+//! Builds the block0 of a new blockchain from its configuration parameters
+//! and initial funds, in order to start local test networks without writing
+//! the genesis file by hand.
+
+use crate::{
+    fee, tx, value, AddressDiscrimination, Block, ConsensusVersion, ErrorKind, Fee, FeeVariant,
+    Fragment, JsChainError, Output, Outputs, PublicKey, Value,
+};
+use chain::block::{BlockDate, BlockVersion, ContentsBuilder, HeaderBuilderNew};
+use chain::config::{Block0Date, ConfigParam};
+use chain::fragment::{ConfigParams, Fragment as ChainFragment};
+use chain::leadership::bft;
+use chain::legacy;
+use chain::milli::Milli;
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// maximum number of outputs of a transaction, or of addresses of a legacy declaration
+const MAX_OUTPUTS: usize = 255;
+
+/// Builder of the genesis block of a new blockchain
+///
+/// ```javascript
+/// const builder = Block0Builder.new(
+///     AddressDiscrimination.Test,
+///     ConsensusVersion.Bft,
+///     Date.now() / 1000,
+///     2,
+///     100
+/// );
+/// builder.set_fee(Fee.linear_fee(Value.from_str(&#39;10&#39;), Value.from_str(&#39;2&#39;), Value.from_str(&#39;5&#39;)));
+/// builder.add_bft_leader(leader_public_key);
+/// builder.add_initial_fund(Output.new(address, Value.from_str(&#39;1000000&#39;)));
+/// const block0 = builder.build();
+/// const bytes = block0.as_bytes();
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Block0Builder {
+    discrimination: chain_addr::Discrimination,
+    consensus_version: ConsensusVersion,
+    block0_date: u64,
+    slot_duration: u8,
+    slots_per_epoch: u32,
+    fee: Option<fee::LinearFee>,
+    kes_update_speed: Option<u32>,
+    active_slots_coeff: Option<Milli>,
+    bft_leaders: Vec<bft::LeaderId>,
+    initial_funds: Vec<tx::Output<chain_addr::Address>>,
+    legacy_funds: Vec<(legacy::OldAddress, value::Value)>,
+    certificates: Vec<ChainFragment>,
+}
+
+impl Block0Builder {
+    fn config_params(&self) -> ConfigParams {
+        let mut params = ConfigParams::new();
+        params.push(ConfigParam::Block0Date(Block0Date(self.block0_date)));
+        params.push(ConfigParam::Discrimination(self.discrimination));
+        params.push(ConfigParam::ConsensusVersion(self.consensus_version.into()));
+        params.push(ConfigParam::SlotDuration(self.slot_duration));
+        params.push(ConfigParam::SlotsPerEpoch(self.slots_per_epoch));
+        if let Some(fee) = self.fee {
+            params.push(ConfigParam::LinearFee(fee));
+        }
+        if let Some(speed) = self.kes_update_speed {
+            params.push(ConfigParam::KESUpdateSpeed(speed));
+        }
+        if let Some(coeff) = self.active_slots_coeff {
+            params.push(ConfigParam::ConsensusGenesisPraosActiveSlotsCoeff(coeff));
+        }
+        for leader in &self.bft_leaders {
+            params.push(ConfigParam::AddBftLeader(leader.clone()));
+        }
+        params
+    }
+
+    /// the initial funds are declared as transactions without inputs
+    fn initial_fund_fragments(&self) -> Vec<ChainFragment> {
+        self.initial_funds
+            .chunks(MAX_OUTPUTS)
+            .map(|outputs| {
+                let transaction = tx::TxBuilder::new()
+                    .set_nopayload()
+                    .set_ios(&[], outputs)
+                    .set_witnesses(&[])
+                    .set_payload_auth(&());
+                ChainFragment::Transaction(transaction)
+            })
+            .collect()
+    }
+
+    fn legacy_fund_fragments(&self) -> Vec<ChainFragment> {
+        self.legacy_funds
+            .chunks(MAX_OUTPUTS)
+            .map(|addrs| {
+                ChainFragment::OldUtxoDeclaration(legacy::UtxoDeclaration {
+                    addrs: addrs.to_vec(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Block0Builder {
+    /// Start a genesis block with the mandatory parameters, the date is in seconds
+    /// since the unix epoch and the slot duration in seconds
+    pub fn new(
+        discrimination: AddressDiscrimination,
+        consensus_version: ConsensusVersion,
+        block0_date: f64,
+        slot_duration: u8,
+        slots_per_epoch: u32,
+    ) -> Block0Builder {
+        Block0Builder {
+            discrimination: discrimination.into(),
+            consensus_version,
+            block0_date: block0_date as u64,
+            slot_duration,
+            slots_per_epoch,
+            fee: None,
+            kes_update_speed: None,
+            active_slots_coeff: None,
+            bft_leaders: Vec::new(),
+            initial_funds: Vec::new(),
+            legacy_funds: Vec::new(),
+            certificates: Vec::new(),
+        }
+    }
+
+    /// Fee of the transactions and certificates, there is no fee if not set
+    pub fn set_fee(&mut self, fee: &Fee) {
+        self.fee = match fee.0 {
+            FeeVariant::Linear(algorithm) => Some(algorithm),
+        };
+    }
+
+    /// Maximum time in seconds between two updates of the KES key of the stake pools
+    pub fn set_kes_update_speed(&mut self, seconds: u32) {
+        self.kes_update_speed = Some(seconds);
+    }
+
+    /// Active slots coefficient of the genesis praos consensus, in thousandths
+    /// (e.g. 100 for 0.1)
+    pub fn set_active_slots_coeff(&mut self, millis: u32) -> Result<(), JsChainError> {
+        if millis == 0 || millis > 1000 {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "Active slots coefficient must be between 1 and 1000 thousandths",
+            ));
+        }
+        self.active_slots_coeff = Some(Milli::from_millis(u64::from(millis)));
+        Ok(())
+    }
+
+    pub fn add_bft_leader(&mut self, leader: &PublicKey) {
+        self.bft_leaders.push(bft::LeaderId::from(leader.0.clone()));
+    }
+
+    pub fn add_initial_fund(&mut self, output: &Output) {
+        self.initial_funds.push(output.0.clone());
+    }
+
+    pub fn add_initial_funds(&mut self, outputs: &Outputs) {
+        self.initial_funds
+            .extend(outputs.0.iter().map(|output| output.0.clone()));
+    }
+
+    /// Declare funds for a legacy (byron) base58 address
+    pub fn add_legacy_fund(&mut self, address: &str, value: &Value) -> Result<(), JsChainError> {
+        let address = address.parse::<legacy::OldAddress>().map_err(|e| {
+            JsChainError::with_source(ErrorKind::InvalidAddress, "Invalid legacy address", e)
+        })?;
+        self.legacy_funds.push((address, value.0));
+        Ok(())
+    }
+
+    /// Add a signed pool registration or stake delegation, the transaction must
+    /// not have inputs nor outputs as there are no funds to spend yet
+    pub fn add_certificate(&mut self, fragment: &Fragment) -> Result<(), JsChainError> {
+        match &fragment.0 {
+            ChainFragment::PoolRegistration(_) | ChainFragment::StakeDelegation(_) => (),
+            _ => {
+                return Err(JsChainError::new(
+                    ErrorKind::WrongType,
+                    "Only pool registrations and stake delegations can be added to block0",
+                ))
+            }
+        }
+        let transaction = fragment.get_transaction()?;
+        if !transaction.0.inputs().is_empty() || !transaction.0.outputs().is_empty() {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "Block0 certificates can't have inputs or outputs",
+            ));
+        }
+        self.certificates.push(fragment.0.clone());
+        Ok(())
+    }
+
+    /// Create the genesis block: the initial fragment followed by the initial
+    /// funds, the legacy funds and the certificates
+    pub fn build(&self) -> Result<Block, JsChainError> {
+        if self.consensus_version == ConsensusVersion::Bft && self.bft_leaders.is_empty() {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "A BFT blockchain needs at least one leader",
+            ));
+        }
+        if self.consensus_version == ConsensusVersion::GenesisPraos
+            && (self.active_slots_coeff.is_none() || self.kes_update_speed.is_none())
+        {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "A genesis praos blockchain needs the active slots coefficient and the KES update speed",
+            ));
+        }
+
+        let mut contents = ContentsBuilder::new();
+        contents.push(ChainFragment::Initial(self.config_params()));
+        for fragment in self
+            .initial_fund_fragments()
+            .into_iter()
+            .chain(self.legacy_fund_fragments())
+            .chain(self.certificates.iter().cloned())
+        {
+            contents.push(fragment);
+        }
+        let contents = contents.into();
+
+        let header = HeaderBuilderNew::new(BlockVersion::Genesis, &contents)
+            .set_genesis()
+            .set_date(BlockDate::first())
+            .into_unsigned_header()
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::InvalidBlock, "Failed to build the header", e)
+            })?
+            .generalize();
+
+        Ok(Block(chain::block::Block { header, contents }))
+    }
+}
@@ -0,0 +1,181 @@
+//! Parsing of blocks without copies, for indexers
+//!
+//! `BlockView` borrows the bytes of a block and only decodes what is asked
+//! for: the header, or the fragments one by one. It has a lifetime so it is
+//! only available from rust, the javascript side gets `BlockStreamDecoder`
+//! to split the stream of blocks sent by the node.
+
+use crate::{key, Block, ErrorKind, Fragment, FragmentId, Header, JsChainError, ReadBuf, Readable};
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Size of the big endian length prefixing each block of a stream
+const BLOCK_LENGTH_SIZE: usize = 4;
+
+/// Largest block accepted from a stream, a bigger length prefix means the
+/// stream is corrupted
+const MAX_BLOCK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Block borrowing its serialized bytes
+pub struct BlockView<'a> {
+    header: &'a [u8],
+    contents: &'a [u8],
+}
+
+impl<'a> BlockView<'a> {
+    /// Only the header is checked, the fragments are checked when iterated
+    pub fn new(bytes: &'a [u8]) -> Result<BlockView<'a>, JsChainError> {
+        let mut buf = ReadBuf::from(bytes);
+        chain::block::Header::read(&mut buf).map_err(|e| {
+            JsChainError::with_source(ErrorKind::Deserialization, "Invalid block header", e)
+        })?;
+        let (header, contents) = bytes.split_at(buf.position());
+        Ok(BlockView { header, contents })
+    }
+
+    pub fn header(&self) -> Result<Header, JsChainError> {
+        Header::from_bytes(self.header)
+    }
+
+    pub fn header_bytes(&self) -> &'a [u8] {
+        self.header
+    }
+
+    /// The serialized fragments, as hashed in the content hash of the header
+    pub fn content_bytes(&self) -> &'a [u8] {
+        self.contents
+    }
+
+    pub fn fragments(&self) -> FragmentViews<'a> {
+        FragmentViews(self.contents)
+    }
+
+    /// Copy the whole block
+    pub fn to_block(&self) -> Result<Block, JsChainError> {
+        let mut bytes = Vec::with_capacity(self.header.len() + self.contents.len());
+        bytes.extend_from_slice(self.header);
+        bytes.extend_from_slice(self.contents);
+        Block::from_bytes(&bytes)
+    }
+}
+
+/// Fragment borrowing its serialized bytes, with their size prefix
+#[derive(Clone, Copy)]
+pub struct FragmentView<'a>(&'a [u8]);
+
+impl<'a> FragmentView<'a> {
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// The id is the hash of the fragment without its size
+    pub fn id(&self) -> FragmentId {
+        key::Hash::hash_bytes(&self.0[2..]).into()
+    }
+
+    pub fn to_fragment(&self) -> Result<Fragment, JsChainError> {
+        Fragment::from_bytes(self.0)
+    }
+}
+
+/// Iterator over the fragments of a `BlockView`, stopping after the first
+/// invalid fragment
+pub struct FragmentViews<'a>(&'a [u8]);
+
+impl<'a> Iterator for FragmentViews<'a> {
+    type Item = Result<FragmentView<'a>, JsChainError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let size = if self.0.len() < 2 {
+            0
+        } else {
+            2 + usize::from(u16::from_be_bytes([self.0[0], self.0[1]]))
+        };
+        if size == 0 || size > self.0.len() {
+            self.0 = &[];
+            return Some(Err(JsChainError::new(
+                ErrorKind::Deserialization,
+                "Invalid fragment, not enough bytes",
+            )));
+        }
+        let (fragment, rest) = self.0.split_at(size);
+        self.0 = rest;
+        Some(Ok(FragmentView(fragment)))
+    }
+}
+
+/// Split a stream of blocks, each prefixed with its size on 4 bytes in big
+/// endian, as they arrive
+///
+/// ```javascript
+/// const decoder = BlockStreamDecoder.new();
+/// response.on(&#39;data&#39;, chunk =&gt; {
+///     decoder.push(chunk);
+///     let block;
+///     while ((block = decoder.next_block())) {
+///         index(block);
+///     }
+/// });
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Default)]
+pub struct BlockStreamDecoder {
+    buffer: Vec<u8>,
+    /// start of the first block not decoded yet in the buffer
+    offset: usize,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl BlockStreamDecoder {
+    pub fn new() -> BlockStreamDecoder {
+        BlockStreamDecoder::default()
+    }
+
+    /// Add the next bytes of the stream
+    pub fn push(&mut self, bytes: &[u8]) {
+        if self.offset > 0 {
+            self.buffer.drain(..self.offset);
+            self.offset = 0;
+        }
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// The next block of the stream, or null if it is not complete yet. The
+    /// stream can't be decoded past an invalid length prefix
+    pub fn next_block(&mut self) -> Result<Option<Block>, JsChainError> {
+        let pending = &self.buffer[self.offset..];
+        if pending.len() < BLOCK_LENGTH_SIZE {
+            return Ok(None);
+        }
+        let mut length = [0u8; BLOCK_LENGTH_SIZE];
+        length.copy_from_slice(&pending[..BLOCK_LENGTH_SIZE]);
+        let length = u32::from_be_bytes(length) as usize;
+        let end = match BLOCK_LENGTH_SIZE.checked_add(length) {
+            Some(end) if length <= MAX_BLOCK_SIZE => end,
+            _ => {
+                return Err(JsChainError::new(
+                    ErrorKind::Deserialization,
+                    format!(
+                        "Invalid block length {}, the maximum is {} bytes",
+                        length, MAX_BLOCK_SIZE
+                    ),
+                ))
+            }
+        };
+        if pending.len() < end {
+            return Ok(None);
+        }
+        let bytes = &pending[BLOCK_LENGTH_SIZE..end];
+        self.offset += end;
+        Block::from_bytes(bytes).map(Some)
+    }
+
+    /// Number of bytes received and not decoded yet
+    pub fn pending_size(&self) -> usize {
+        self.buffer.len() - self.offset
+    }
+}
@@ -0,0 +1,101 @@
+//! Proof a fragment is in a block
+//!
+//! The header of a block commits to its content with a hash of the
+//! serialized fragments, one after the other. This is a plain hash and not a
+//! merkle tree, so there is no compact proof of inclusion: the proof is all
+//! the fragments of the block, enough to recompute the content hash of the
+//! header without the rest of the chain. Its size is the one of the block.
+
+use crate::{Block, ErrorKind, Fragment, FragmentId, Header, JsChainError, ReadBuf, Readable};
+use chain::block::{Contents, ContentsBuilder};
+use chain_core::property::{Fragment as _, Serialize as _};
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// All the fragments of a block, checked against the header of the block
+///
+/// ```javascript
+/// const proof = block.contents_proof(fragment_id);
+/// // later, offline, with only the header of the block
+/// const contents = BlockContentsProof.from_bytes(proof.as_bytes());
+/// if (!contents.verify(header, fragment_id)) {
+///     throw new Error(&#39;the fragment is not in the block&#39;);
+/// }
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct BlockContentsProof(Vec<chain::fragment::Fragment>);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl BlockContentsProof {
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlockContentsProof, JsChainError> {
+        let mut buf = ReadBuf::from(bytes);
+        let mut fragments = Vec::new();
+        while !buf.is_end() {
+            let fragment = chain::fragment::Fragment::read(&mut buf).map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid proof", e)
+            })?;
+            fragments.push(fragment);
+        }
+        Ok(BlockContentsProof(fragments))
+    }
+
+    /// The serialized fragments, the size is the content size of the header
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for fragment in &self.0 {
+            fragment.serialize(&mut bytes).unwrap();
+        }
+        bytes
+    }
+
+    /// Check the fragment is in the block of the header
+    pub fn verify(&self, header: &Header, fragment_id: &FragmentId) -> bool {
+        let mut contents = ContentsBuilder::new();
+        for fragment in &self.0 {
+            contents.push(fragment.clone());
+        }
+        matches_header(&contents.into(), &header.0)
+            && self.0.iter().any(|fragment| fragment.id() == fragment_id.0)
+    }
+
+    /// The fragment of the proof with the given id
+    pub fn fragment(&self, fragment_id: &FragmentId) -> Option<Fragment> {
+        self.0
+            .iter()
+            .find(|fragment| fragment.id() == fragment_id.0)
+            .cloned()
+            .map(Fragment::from)
+    }
+}
+
+fn matches_header(contents: &Contents, header: &chain::block::Header) -> bool {
+    let (content_hash, content_size) = contents.compute_hash_size();
+    content_hash == header.block_content_hash() && content_size == header.block_content_size()
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Block {
+    /// Recompute the content hash and size of the fragments and compare them
+    /// to the ones of the header
+    pub fn verify_content(&self) -> bool {
+        matches_header(&self.0.contents, &self.0.header)
+    }
+
+    pub fn contents_proof(
+        &self,
+        fragment_id: &FragmentId,
+    ) -> Result<BlockContentsProof, JsChainError> {
+        if !self
+            .0
+            .fragments()
+            .any(|fragment| fragment.id() == fragment_id.0)
+        {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "The fragment is not in the block",
+            ));
+        }
+        Ok(BlockContentsProof(self.0.fragments().cloned().collect()))
+    }
+}
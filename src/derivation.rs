@@ -0,0 +1,221 @@
+//! BIP32 derivation paths
+//!
+//! Paths are written in the usual `m/44'/1815'/0'/0/0` format, where a
+//! trailing `'` (or `h`/`H`) marks a hardened index.
+
+use crate::{ErrorKind, JsChainError};
+use std::fmt;
+use std::str::FromStr;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// first hardened derivation index, every index above is hardened
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// coin type assigned to ADA in SLIP-0044
+pub const ADA_COIN_TYPE: u32 = 1815;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    MissingRoot,
+    InvalidIndex(String),
+    IndexOutOfBounds(u32),
+    HardenedDerivationFromPublicKey(u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingRoot => write!(f, "Derivation path must start with 'm'"),
+            Error::InvalidIndex(segment) => write!(f, "Invalid derivation index: {}", segment),
+            Error::IndexOutOfBounds(index) => write!(
+                f,
+                "Derivation index {} is too big, it must be lower than {}",
+                index, HARDENED
+            ),
+            Error::HardenedDerivationFromPublicKey(index) => write!(
+                f,
+                "Cannot derive hardened index {}' from a public key",
+                index - HARDENED
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for JsChainError {
+    fn from(error: Error) -> JsChainError {
+        JsChainError::new(ErrorKind::Derivation, format!("{}", error))
+    }
+}
+
+/// Purpose of the first level of the standard derivation layouts
+///
+/// * Bip44: legacy (icarus/yoroi style) wallets, `m/44'/1815'/account'/role/index`
+/// * Cip1852: shelley wallets, `m/1852'/1815'/account'/role/index`
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerivationPurpose {
+    Bip44 = 44,
+    Cip1852 = 1852,
+}
+
+/// Chain of addresses in the standard account layout
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerivationRole {
+    External = 0,
+    Internal = 1,
+    Staking = 2,
+}
+
+/// Sequence of BIP32 derivation indices, starting at the root key `m`
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    pub(crate) fn indices(&self) -> &[u32] {
+        &self.0
+    }
+
+    fn append(mut self, index: u32) -> Self {
+        self.0.push(index);
+        self
+    }
+}
+
+impl From<Vec<u32>> for DerivationPath {
+    fn from(indices: Vec<u32>) -> DerivationPath {
+        DerivationPath(indices)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if *index >= HARDENED {
+                write!(f, "/{}'", index - HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = s.trim().split('/');
+        match segments.next() {
+            Some("m") => (),
+            _ => return Err(Error::MissingRoot),
+        }
+
+        segments
+            .map(|segment| {
+                let (number, hardened) = if segment.ends_with(|c| c == '\'' || c == 'h' || c == 'H')
+                {
+                    (&segment[..segment.len() - 1], true)
+                } else {
+                    (segment, false)
+                };
+                let index = number
+                    .parse::<u32>()
+                    .map_err(|_| Error::InvalidIndex(segment.to_owned()))?;
+                if index >= HARDENED {
+                    return Err(Error::IndexOutOfBounds(index));
+                }
+                Ok(if hardened { index + HARDENED } else { index })
+            })
+            .collect::<Result<Vec<u32>, Error>>()
+            .map(DerivationPath)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl DerivationPath {
+    /// The root path `m`, with no derivation
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new() -> DerivationPath {
+        DerivationPath::default()
+    }
+
+    /// Parse a path like `m/1852'/1815'/0'/0/0`
+    pub fn from_string(path: &str) -> Result<DerivationPath, JsChainError> {
+        path.parse().map_err(JsChainError::from)
+    }
+
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = to_string))]
+    pub fn to_js_string(&self) -> String {
+        format!("{}", self)
+    }
+
+    /// Path of an account in the standard layout: `m/purpose'/1815'/account'`
+    pub fn account(
+        purpose: DerivationPurpose,
+        account: u32,
+    ) -> Result<DerivationPath, JsChainError> {
+        if account >= HARDENED {
+            return Err(Error::IndexOutOfBounds(account).into());
+        }
+        Ok(DerivationPath::new()
+            .append(purpose as u32 + HARDENED)
+            .append(ADA_COIN_TYPE + HARDENED)
+            .append(account + HARDENED))
+    }
+
+    /// Path of an address key in the standard layout: `m/purpose'/1815'/account'/role/index`
+    pub fn address(
+        purpose: DerivationPurpose,
+        account: u32,
+        role: DerivationRole,
+        index: u32,
+    ) -> Result<DerivationPath, JsChainError> {
+        if index >= HARDENED {
+            return Err(Error::IndexOutOfBounds(index).into());
+        }
+        DerivationPath::account(purpose, account).map(|path| path.append(role as u32).append(index))
+    }
+
+    /// Add a soft derivation index (lower than 0x80000000) at the end of the path
+    pub fn add_soft(&mut self, index: u32) -> Result<(), JsChainError> {
+        if index >= HARDENED {
+            return Err(Error::IndexOutOfBounds(index).into());
+        }
+        self.0.push(index);
+        Ok(())
+    }
+
+    /// Add a hardened derivation index at the end of the path, the given index is
+    /// the one written in the path (i.e. without the 0x80000000 offset)
+    pub fn add_hardened(&mut self, index: u32) -> Result<(), JsChainError> {
+        if index >= HARDENED {
+            return Err(Error::IndexOutOfBounds(index).into());
+        }
+        self.0.push(index + HARDENED);
+        Ok(())
+    }
+
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Raw index at the given depth, hardened indices include the 0x80000000 offset
+    pub fn get(&self, depth: usize) -> u32 {
+        self.0[depth]
+    }
+
+    pub fn is_hardened(&self, depth: usize) -> bool {
+        self.0[depth] >= HARDENED
+    }
+
+    /// True if every index of the path can be derived from a public key
+    pub fn is_soft(&self) -> bool {
+        self.0.iter().all(|index| *index < HARDENED)
+    }
+}
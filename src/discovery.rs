@@ -0,0 +1,324 @@
+//! HD wallet address discovery
+//!
+//! Derives the external (role 0) and internal (role 1) address chains of an
+//! account from its public key, and keeps a window of `gap_limit` unused
+//! addresses after the last one seen on the blockchain, as described in
+//! [BIP44](https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki#address-gap-limit).
+
+use crate::{
+    tx, Address, AddressDiscrimination, Addresses, Bip32PublicKey, Block, DerivationPath,
+    ErrorKind, Fragment, JsChainError, PublicKey,
+};
+use chain_crypto as crypto;
+use std::collections::HashMap;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// the gap limit recommended by BIP44
+const DEFAULT_GAP_LIMIT: u32 = 20;
+
+const EXTERNAL_ROLE: u32 = 0;
+const INTERNAL_ROLE: u32 = 1;
+
+/// Kind of address generated for each derived key
+#[derive(Clone)]
+enum Scheme {
+    Single,
+    Group(crypto::PublicKey<crypto::Ed25519>),
+    Account,
+}
+
+struct AddressChain {
+    chain_key: crypto::PublicKey<crypto::Ed25519Bip32>,
+    addresses: Vec<chain_addr::Address>,
+    used: Vec<bool>,
+}
+
+impl AddressChain {
+    fn new(account_key: &crypto::PublicKey<crypto::Ed25519Bip32>, role: u32) -> Self {
+        AddressChain {
+            chain_key: crypto::derive::derive_pk_ed25519(account_key, role)
+                .expect("role is always a soft derivation index"),
+            addresses: Vec::new(),
+            used: Vec::new(),
+        }
+    }
+
+    /// number of addresses up to (and including) the last used one
+    fn used_window(&self) -> usize {
+        self.used
+            .iter()
+            .rposition(|used| *used)
+            .map(|index| index + 1)
+            .unwrap_or(0)
+    }
+
+    fn address_key(&self, index: u32) -> crypto::PublicKey<crypto::Ed25519> {
+        let key = crypto::derive::derive_pk_ed25519(&self.chain_key, index)
+            .expect("address indices are always soft derivation indices");
+        crypto::derive::to_raw_pk(&key)
+    }
+}
+
+/// Discover the addresses of an account given its public key
+/// (`m/purpose'/1815'/account'`)
+///
+/// ```javascript
+/// const discovery = AccountDiscovery.single(account_key, AddressDiscrimination.Test, 20);
+/// blocks.forEach(block => discovery.scan_block(block));
+/// const used = discovery.used_addresses();
+/// const receive = discovery.next_external_address();
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct AccountDiscovery {
+    discrimination: chain_addr::Discrimination,
+    scheme: Scheme,
+    gap_limit: u32,
+    external: AddressChain,
+    internal: AddressChain,
+    lookup: HashMap<chain_addr::Address, (u32, u32)>,
+}
+
+impl AccountDiscovery {
+    fn new(
+        account_key: &Bip32PublicKey,
+        discrimination: AddressDiscrimination,
+        scheme: Scheme,
+        gap_limit: u32,
+    ) -> Result<Self, JsChainError> {
+        if gap_limit == 0 {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "Gap limit must be greater than zero",
+            ));
+        }
+        let mut discovery = AccountDiscovery {
+            discrimination: discrimination.into(),
+            scheme,
+            gap_limit,
+            external: AddressChain::new(&account_key.0, EXTERNAL_ROLE),
+            internal: AddressChain::new(&account_key.0, INTERNAL_ROLE),
+            lookup: HashMap::new(),
+        };
+        discovery.fill_gap();
+        Ok(discovery)
+    }
+
+    fn chain_mut(&mut self, role: u32) -> &mut AddressChain {
+        match role {
+            EXTERNAL_ROLE => &mut self.external,
+            _ => &mut self.internal,
+        }
+    }
+
+    fn to_address(&self, key: crypto::PublicKey<crypto::Ed25519>) -> chain_addr::Address {
+        let kind = match &self.scheme {
+            Scheme::Single => chain_addr::Kind::Single(key),
+            Scheme::Group(delegation) => chain_addr::Kind::Group(key, delegation.clone()),
+            Scheme::Account => chain_addr::Kind::Account(key),
+        };
+        chain_addr::Address(self.discrimination, kind)
+    }
+
+    /// derive addresses until both chains have `gap_limit` unused addresses
+    /// after their last used one
+    fn fill_gap(&mut self) {
+        for role in &[EXTERNAL_ROLE, INTERNAL_ROLE] {
+            let gap_limit = self.gap_limit as usize;
+            loop {
+                let chain = self.chain_mut(*role);
+                if chain.addresses.len() >= chain.used_window() + gap_limit {
+                    break;
+                }
+                let index = chain.addresses.len() as u32;
+                let key = chain.address_key(index);
+                let address = self.to_address(key);
+
+                let chain = self.chain_mut(*role);
+                chain.addresses.push(address.clone());
+                chain.used.push(false);
+                self.lookup.insert(address, (*role, index));
+            }
+        }
+    }
+
+    /// mark the address as used, returns true if the address belongs to the account
+    /// and it was not used before
+    fn mark_used(&mut self, address: &chain_addr::Address) -> bool {
+        let (role, index) = match self.lookup.get(address) {
+            Some(position) => *position,
+            None => return false,
+        };
+        let chain = self.chain_mut(role);
+        let newly_used = !chain.used[index as usize];
+        chain.used[index as usize] = true;
+        if newly_used {
+            self.fill_gap();
+        }
+        newly_used
+    }
+
+    fn seen_addresses(&self, fragment: &Fragment) -> Vec<chain_addr::Address> {
+        let transaction = match fragment.get_transaction() {
+            Ok(transaction) => transaction,
+            Err(_) => return Vec::new(),
+        };
+
+        let outputs = transaction
+            .outputs()
+            .0
+            .into_iter()
+            .map(|output| output.0.address);
+
+        // spending from an account is the only way an input reveals its owner
+        let accounts = transaction
+            .inputs()
+            .0
+            .into_iter()
+            .filter_map(|input| match input.0.to_enum() {
+                tx::InputEnum::AccountInput(id, _) => id.to_single_account(),
+                tx::InputEnum::UtxoInput(_) => None,
+            })
+            .map(|id| {
+                chain_addr::Address(self.discrimination, chain_addr::Kind::Account(id.into()))
+            });
+
+        outputs.chain(accounts).collect()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl AccountDiscovery {
+    /// Discover single (utxo without delegation) addresses
+    pub fn single(
+        account_key: &Bip32PublicKey,
+        discrimination: AddressDiscrimination,
+        gap_limit: u32,
+    ) -> Result<AccountDiscovery, JsChainError> {
+        AccountDiscovery::new(account_key, discrimination, Scheme::Single, gap_limit)
+    }
+
+    /// Discover group (utxo with delegation) addresses, all delegating to the given key
+    pub fn group(
+        account_key: &Bip32PublicKey,
+        delegation_key: &PublicKey,
+        discrimination: AddressDiscrimination,
+        gap_limit: u32,
+    ) -> Result<AccountDiscovery, JsChainError> {
+        AccountDiscovery::new(
+            account_key,
+            discrimination,
+            Scheme::Group(delegation_key.0.clone()),
+            gap_limit,
+        )
+    }
+
+    /// Discover account addresses, each derived key being one account
+    pub fn account(
+        account_key: &Bip32PublicKey,
+        discrimination: AddressDiscrimination,
+        gap_limit: u32,
+    ) -> Result<AccountDiscovery, JsChainError> {
+        AccountDiscovery::new(account_key, discrimination, Scheme::Account, gap_limit)
+    }
+
+    /// The gap limit recommended by BIP44 (20)
+    pub fn default_gap_limit() -> u32 {
+        DEFAULT_GAP_LIMIT
+    }
+
+    pub fn gap_limit(&self) -> u32 {
+        self.gap_limit
+    }
+
+    /// Look for the account addresses in the given fragment, returns true if a
+    /// new address was found. Fragments must be scanned in blockchain order.
+    pub fn scan_fragment(&mut self, fragment: &Fragment) -> bool {
+        let mut found = false;
+        // marking an address as used extends the chains, so an address of the
+        // same fragment that was out of the window may now be known
+        loop {
+            let newly_used = self
+                .seen_addresses(fragment)
+                .iter()
+                .fold(false, |acc, address| self.mark_used(address) || acc);
+            if !newly_used {
+                break found;
+            }
+            found = true;
+        }
+    }
+
+    /// Scan all the fragments of the block, returns true if a new address was found
+    pub fn scan_block(&mut self, block: &Block) -> bool {
+        block
+            .fragments()
+            .0
+            .iter()
+            .fold(false, |acc, fragment| self.scan_fragment(fragment) || acc)
+    }
+
+    /// All derived external (receiving) addresses, including the unused lookahead ones
+    pub fn external_addresses(&self) -> Addresses {
+        self.external
+            .addresses
+            .iter()
+            .cloned()
+            .map(Address)
+            .collect::<Vec<Address>>()
+            .into()
+    }
+
+    /// All derived internal (change) addresses, including the unused lookahead ones
+    pub fn internal_addresses(&self) -> Addresses {
+        self.internal
+            .addresses
+            .iter()
+            .cloned()
+            .map(Address)
+            .collect::<Vec<Address>>()
+            .into()
+    }
+
+    /// Addresses of both chains that have been seen on the blockchain
+    pub fn used_addresses(&self) -> Addresses {
+        let used = |chain: &AddressChain| {
+            chain
+                .addresses
+                .iter()
+                .zip(chain.used.iter())
+                .filter(|(_, used)| **used)
+                .map(|(address, _)| Address(address.clone()))
+                .collect::<Vec<Address>>()
+        };
+        let mut addresses = used(&self.external);
+        addresses.extend(used(&self.internal));
+        addresses.into()
+    }
+
+    pub fn is_used(&self, address: &Address) -> bool {
+        match self.lookup.get(&address.0) {
+            Some((EXTERNAL_ROLE, index)) => self.external.used[*index as usize],
+            Some((_, index)) => self.internal.used[*index as usize],
+            None => false,
+        }
+    }
+
+    /// First external address after the last used one
+    pub fn next_external_address(&self) -> Address {
+        Address(self.external.addresses[self.external.used_window()].clone())
+    }
+
+    /// First internal address after the last used one, to be used as change address
+    pub fn next_internal_address(&self) -> Address {
+        Address(self.internal.addresses[self.internal.used_window()].clone())
+    }
+
+    /// Path (`m/role/index`) of the address relative to the account key, if it
+    /// belongs to the discovered chains
+    pub fn address_path(&self, address: &Address) -> Option<DerivationPath> {
+        self.lookup
+            .get(&address.0)
+            .map(|(role, index)| DerivationPath::from(vec![*role, *index]))
+    }
+}
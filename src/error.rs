@@ -0,0 +1,126 @@
+//! Error returned by every fallible function of the library
+//!
+//! The `kind` is stable between releases and meant to be matched on by the
+//! callers, the message and the source are for humans only.
+
+use std::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Category of a `JsChainError`
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// invalid bech32 string, or with an unexpected prefix
+    Bech32,
+    /// invalid hexadecimal string
+    Hex,
+    /// byte array or string of an unexpected size
+    InvalidLength,
+    /// invalid binary encoding
+    Deserialization,
+    Serialization,
+    /// bytes that are not a valid key
+    InvalidKey,
+    InvalidAddress,
+    /// an account address was expected
+    NotAccountAddress,
+    /// invalid number, or arithmetic overflow
+    InvalidValue,
+    /// the inputs don't cover the outputs and the fee
+    Balance,
+    FeeAlgorithm,
+    /// too many inputs, outputs or witnesses in a transaction
+    TransactionBuilder,
+    /// the payload authentication doesn't match the payload of the transaction
+    WrongPayloadAuth,
+    /// the object is not of the requested type (fragment, certificate, input...)
+    WrongType,
+    Mnemonic,
+    Derivation,
+    RandomGenerator,
+    /// the block can't be applied (not the genesis block, not a child of the tip...)
+    InvalidBlock,
+    /// the ledger rejected the block or fragment
+    Ledger,
+    InvalidArgument,
+    /// the external signer of a transaction failed or returned an invalid value
+    Signer,
+}
+
+/// Error thrown by the functions of the library
+///
+/// ```javascript
+/// try {
+///     Address.from_string(&#39;not an address&#39;);
+/// } catch (e) {
+///     if (e.kind() === ErrorKind.InvalidAddress) {
+///         console.log(e.message());
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsChainError {
+    kind: ErrorKind,
+    message: String,
+    source: Option<String>,
+}
+
+impl JsChainError {
+    pub(crate) fn new<M: Into<String>>(kind: ErrorKind, message: M) -> Self {
+        JsChainError {
+            kind,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// error caused by another one, typically from the chain libraries
+    pub(crate) fn with_source<M: Into<String>, E: fmt::Display>(
+        kind: ErrorKind,
+        message: M,
+        source: E,
+    ) -> Self {
+        JsChainError {
+            kind,
+            message: message.into(),
+            source: Some(format!("{}", source)),
+        }
+    }
+}
+
+impl fmt::Display for JsChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.source {
+            Some(source) => write!(f, "{}: {}", self.message, source),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for JsChainError {}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl JsChainError {
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    /// Description of the underlying error, if any. Named `source` in
+    /// javascript, `std::error::Error::source` is kept for the rust callers
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = source))]
+    pub fn source_message(&self) -> Option<String> {
+        self.source.clone()
+    }
+
+    /// The message followed by the source
+    #[cfg_attr(feature = "wasm", wasm_bindgen(js_name = toString))]
+    pub fn to_js_string(&self) -> String {
+        format!("{}", self)
+    }
+}
@@ -0,0 +1,177 @@
+//! Block headers
+//!
+//! The header is what the leader of the slot signs: a BFT leader with its
+//! ed25519 key, a genesis praos stake pool with its KES key, after proving
+//! its leadership of the slot with its VRF key.
+
+use crate::{
+    Block, BlockDate, BlockId, ErrorKind, GenesisPraosLeader, Hash, JsChainError, PoolId, PublicKey,
+};
+use chain::block::{BlockVersion, Proof};
+use chain_core::property::{Deserialize as _, Serialize as _};
+use chain_crypto as crypto;
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Consensus the block was created with, which gives the proof in the header
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderVersion {
+    /// the genesis block, without proof
+    Genesis,
+    /// signed by a BFT leader
+    Bft,
+    /// signed by a stake pool, with the proof of its leadership
+    GenesisPraos,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct Header(pub(crate) chain::block::Header);
+
+impl From<chain::block::Header> for Header {
+    fn from(header: chain::block::Header) -> Header {
+        Header(header)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Header {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Header, JsChainError> {
+        chain::block::Header::deserialize(bytes)
+            .map_err(|e| JsChainError::with_source(ErrorKind::Deserialization, "Invalid header", e))
+            .map(Header)
+    }
+
+    pub fn as_bytes(&self) -> Result<Vec<u8>, JsChainError> {
+        self.0.serialize_as_vec().map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::Serialization,
+                "Failed to serialize the header",
+                e,
+            )
+        })
+    }
+
+    /// The id of the block
+    pub fn id(&self) -> BlockId {
+        self.0.hash().into()
+    }
+
+    pub fn parent_id(&self) -> BlockId {
+        self.0.block_parent_hash().into()
+    }
+
+    pub fn version(&self) -> HeaderVersion {
+        match self.0.block_version() {
+            BlockVersion::Genesis => HeaderVersion::Genesis,
+            BlockVersion::Ed25519Signed => HeaderVersion::Bft,
+            BlockVersion::KesVrfproof => HeaderVersion::GenesisPraos,
+        }
+    }
+
+    pub fn date(&self) -> BlockDate {
+        self.0.block_date().into()
+    }
+
+    pub fn chain_length(&self) -> u32 {
+        u32::from(self.0.chain_length())
+    }
+
+    /// Hash of the fragments of the block
+    pub fn content_hash(&self) -> Hash {
+        self.0.block_content_hash().into()
+    }
+
+    pub fn content_size(&self) -> u32 {
+        self.0.block_content_size()
+    }
+
+    /// Public key of the leader of a BFT block
+    pub fn bft_leader(&self) -> Option<PublicKey> {
+        Some(PublicKey(
+            self.0.get_bft_leader_id()?.as_public_key().clone(),
+        ))
+    }
+
+    /// Stake pool of a genesis praos block
+    pub fn pool_id(&self) -> Option<PoolId> {
+        Some(self.0.get_stakepool_id()?.into())
+    }
+
+    /// Signature of a BFT block
+    pub fn bft_signature(&self) -> Option<Vec<u8>> {
+        match self.0.proof() {
+            Proof::Bft(proof) => Some(proof.signature.0.as_ref().to_vec()),
+            _ => None,
+        }
+    }
+
+    /// Proof of the leadership of the stake pool of a genesis praos block
+    pub fn vrf_proof(&self) -> Option<Vec<u8>> {
+        match self.0.proof() {
+            Proof::GenesisPraos(proof) => Some(proof.vrf_proof.bytes().to_vec()),
+            _ => None,
+        }
+    }
+
+    /// KES signature of a genesis praos block
+    pub fn kes_signature(&self) -> Option<Vec<u8>> {
+        match self.0.proof() {
+            Proof::GenesisPraos(proof) => Some(proof.kes_proof.0.as_ref().to_vec()),
+            _ => None,
+        }
+    }
+
+    /// The bytes of the header signed by the leader
+    pub fn signed_bytes(&self) -> Vec<u8> {
+        self.0.as_auth_slice().to_vec()
+    }
+
+    /// Check the header is signed by the given BFT leader
+    pub fn verify_bft(&self, leader: &PublicKey) -> Result<bool, JsChainError> {
+        match self.0.proof() {
+            Proof::Bft(proof) => Ok(proof.leader_id.as_public_key() == &leader.0
+                && proof
+                    .signature
+                    .0
+                    .verify_slice(&leader.0, self.0.as_auth_slice())
+                    == crypto::Verification::Success),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "The header is not signed by a BFT leader",
+            )),
+        }
+    }
+
+    /// Check the header is signed by the given stake pool, with the KES key
+    /// of its leader. Checking the VRF proof of the leadership needs the
+    /// nonce and the stake distribution of the epoch, see
+    /// `LeaderScheduleCalculator`
+    pub fn verify_genesis_praos(
+        &self,
+        pool_id: &PoolId,
+        leader: &GenesisPraosLeader,
+    ) -> Result<bool, JsChainError> {
+        match self.0.proof() {
+            Proof::GenesisPraos(proof) => Ok(proof.node_id == pool_id.0
+                && proof
+                    .kes_proof
+                    .0
+                    .verify_slice(&leader.0.kes_public_key, self.0.as_auth_slice())
+                    == crypto::Verification::Success),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "The header is not signed by a stake pool",
+            )),
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Block {
+    pub fn header(&self) -> Header {
+        Header(self.0.header.clone())
+    }
+}
@@ -0,0 +1,121 @@
+//! Chain of block headers, for light clients
+//!
+//! The chain starts from a trusted header (the genesis block or a checkpoint)
+//! and only accepts the headers that extend a header already known, so a
+//! wallet can follow the blockchain without the ledger. When there are forks
+//! all the branches are kept, and the tip is the end of the longest one.
+
+use crate::{Block, BlockId, Header};
+use chain::block::HeaderId;
+use chain_impl_mockchain as chain;
+use std::collections::HashMap;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Result of adding a header to the chain
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeaderStatus {
+    /// the header is accepted and is the new tip of the chain
+    Tip,
+    /// the header is accepted on a branch shorter than the one of the tip
+    Fork,
+    AlreadyKnown,
+    /// the parent of the header is not in the chain
+    UnknownParent,
+    /// the chain length is not the one of the parent plus one
+    WrongChainLength,
+    /// the date is not after the one of the parent
+    DateNotIncreasing,
+    /// the content hash or size of the header doesn't match the block
+    ContentMismatch,
+}
+
+/// Headers accepted from a trusted root
+///
+/// ```javascript
+/// const chain = HeaderChain.new(block0.header());
+/// const status = chain.add_header(Header.from_bytes(bytes));
+/// if (status !== HeaderStatus.Tip &amp;&amp; status !== HeaderStatus.Fork) {
+///     console.log(&#39;rejected header&#39;, status);
+/// }
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct HeaderChain {
+    headers: HashMap<HeaderId, chain::block::Header>,
+    tip: HeaderId,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl HeaderChain {
+    pub fn new(root: &Header) -> HeaderChain {
+        let tip = root.0.hash();
+        let mut headers = HashMap::new();
+        headers.insert(tip, root.0.clone());
+        HeaderChain { headers, tip }
+    }
+
+    /// Check the header against its parent and add it to the chain if valid
+    pub fn add_header(&mut self, header: &Header) -> HeaderStatus {
+        let id = header.0.hash();
+        if self.headers.contains_key(&id) {
+            return HeaderStatus::AlreadyKnown;
+        }
+        let parent = match self.headers.get(&header.0.block_parent_hash()) {
+            Some(parent) => parent,
+            None => return HeaderStatus::UnknownParent,
+        };
+        if u32::from(header.0.chain_length()) != u32::from(parent.chain_length()) + 1 {
+            return HeaderStatus::WrongChainLength;
+        }
+        if header.0.block_date() <= parent.block_date() {
+            return HeaderStatus::DateNotIncreasing;
+        }
+        let longest = u32::from(header.0.chain_length()) > self.length();
+        self.headers.insert(id, header.0.clone());
+        if longest {
+            self.tip = id;
+            HeaderStatus::Tip
+        } else {
+            HeaderStatus::Fork
+        }
+    }
+
+    /// Same as `add_header`, checking the content of the block against its header
+    pub fn add_block(&mut self, block: &Block) -> HeaderStatus {
+        if !block.verify_content() {
+            return HeaderStatus::ContentMismatch;
+        }
+        self.add_header(&block.header())
+    }
+
+    pub fn tip(&self) -> Header {
+        Header(self.headers[&self.tip].clone())
+    }
+
+    /// Chain length of the tip
+    pub fn length(&self) -> u32 {
+        u32::from(self.headers[&self.tip].chain_length())
+    }
+
+    pub fn get(&self, id: &BlockId) -> Option<Header> {
+        self.headers.get(&id.0).cloned().map(Header)
+    }
+
+    /// Whether the block is an ancestor of the tip, or the tip itself
+    pub fn is_in_main_chain(&self, id: &BlockId) -> bool {
+        let mut current = Some(&self.headers[&self.tip]);
+        while let Some(header) = current {
+            if header.hash() == id.0 {
+                return true;
+            }
+            current = self.headers.get(&header.block_parent_hash());
+        }
+        false
+    }
+
+    /// Number of headers in all the branches
+    pub fn size(&self) -> usize {
+        self.headers.len()
+    }
+}
@@ -0,0 +1,245 @@
+//! Transaction history of a wallet, from the blocks of the chain
+//!
+//! The builder is fed with the blocks in order and keeps the outputs sent to
+//! the watched addresses, so the utxo inputs spending them can be recognized
+//! later without asking the node. The inputs spending other outputs are not
+//! resolved: only their value is known, from the input itself.
+
+use crate::{
+    tx, value, Account, Address, AddressDiscrimination, Addresses, Block, BlockDate,
+    CertificateKind, FragmentId, Value,
+};
+use chain::fragment::Fragment;
+use chain_core::property::Fragment as _;
+use chain_impl_mockchain as chain;
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Movement of the funds of the wallet in a transaction
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionDirection {
+    /// none of the inputs belongs to the wallet
+    Incoming,
+    /// some of the outputs are sent out of the wallet
+    Outgoing,
+    /// all the inputs and the outputs belong to the wallet
+    SelfTransfer,
+}
+
+/// A transaction of the history, from the point of view of the wallet
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct TransactionRecord {
+    fragment_id: chain::fragment::FragmentId,
+    date: BlockDate,
+    direction: TransactionDirection,
+    received: value::Value,
+    sent: value::Value,
+    fee: value::Value,
+    counterparties: Vec<chain_addr::Address>,
+    certificate_kind: Option<CertificateKind>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TransactionRecord {
+    pub fn fragment_id(&self) -> FragmentId {
+        self.fragment_id.into()
+    }
+
+    pub fn date(&self) -> BlockDate {
+        self.date
+    }
+
+    pub fn direction(&self) -> TransactionDirection {
+        self.direction
+    }
+
+    /// Sum of the outputs sent to the wallet
+    pub fn received(&self) -> Value {
+        self.received.into()
+    }
+
+    /// Sum of the inputs spending funds of the wallet
+    pub fn sent(&self) -> Value {
+        self.sent.into()
+    }
+
+    /// Received minus sent, as a signed decimal string
+    pub fn net_change(&self) -> String {
+        let (received, sent) = (*self.received.as_ref(), *self.sent.as_ref());
+        if received >= sent {
+            format!("{}", received - sent)
+        } else {
+            format!("-{}", sent - received)
+        }
+    }
+
+    /// Inputs minus outputs of the whole transaction
+    pub fn fee(&self) -> Value {
+        self.fee.into()
+    }
+
+    /// The addresses of the outputs sent out of the wallet, and of the
+    /// single accounts the funds are received from. The utxo inputs of other
+    /// wallets can't be resolved, so their addresses are not known.
+    pub fn counterparties(&self) -> Addresses {
+        self.counterparties
+            .iter()
+            .cloned()
+            .map(Address)
+            .collect::<Vec<Address>>()
+            .into()
+    }
+
+    pub fn certificate_kind(&self) -> Option<CertificateKind> {
+        self.certificate_kind
+    }
+}
+
+crate::impl_collection!(TransactionRecords, TransactionRecord);
+
+/// Build the transaction history of a set of addresses and accounts
+///
+/// ```javascript
+/// const history = HistoryBuilder.new(AddressDiscrimination.Test);
+/// history.watch_address(address);
+/// history.watch_account(account);
+/// blocks.forEach(block =&gt; history.add_block(block));
+/// const records = history.records();
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct HistoryBuilder {
+    discrimination: chain_addr::Discrimination,
+    watched: HashSet<chain_addr::Address>,
+    accounts: Vec<tx::UnspecifiedAccountIdentifier>,
+    unspent: HashMap<(chain::fragment::FragmentId, u8), value::Value>,
+    records: Vec<TransactionRecord>,
+}
+
+impl HistoryBuilder {
+    fn record(&mut self, fragment: &Fragment, date: BlockDate) -> Option<TransactionRecord> {
+        let certificate_kind = match fragment {
+            Fragment::Transaction(_) => None,
+            Fragment::StakeDelegation(_) => Some(CertificateKind::StakeDelegation),
+            Fragment::OwnerStakeDelegation(_) => Some(CertificateKind::OwnerStakeDelegation),
+            Fragment::PoolRegistration(_) => Some(CertificateKind::PoolRegistration),
+            Fragment::PoolRetirement(_) => Some(CertificateKind::PoolRetirement),
+            Fragment::PoolUpdate(_) => Some(CertificateKind::PoolUpdate),
+            _ => return None,
+        };
+        let transaction = crate::Fragment::from(fragment.clone())
+            .get_transaction()
+            .ok()?;
+        let fragment_id = fragment.id();
+
+        // the blocks are not validated, so the sums saturate instead of
+        // overflowing
+        let mut inputs_total = 0u64;
+        let mut sent = 0u64;
+        let mut counterparties = Vec::new();
+        for input in transaction.0.inputs() {
+            let value = *input.value().as_ref();
+            inputs_total = inputs_total.saturating_add(value);
+            match input.to_enum() {
+                tx::InputEnum::UtxoInput(pointer) => {
+                    let key = (pointer.transaction_id, pointer.output_index);
+                    if self.unspent.remove(&key).is_some() {
+                        sent = sent.saturating_add(value);
+                    }
+                }
+                tx::InputEnum::AccountInput(account, _) => {
+                    if self.accounts.contains(&account) {
+                        sent = sent.saturating_add(value);
+                    } else if let Some(single) = account.to_single_account() {
+                        let address = Account(tx::AccountIdentifier::Single(single))
+                            .to_address(self.discrimination.into());
+                        counterparties.push(address.0);
+                    }
+                }
+            }
+        }
+
+        let mut outputs_total = 0u64;
+        let mut received = 0u64;
+        let mut sent_out = false;
+        for (index, output) in transaction.0.outputs().into_iter().enumerate() {
+            let value = *output.value.as_ref();
+            outputs_total = outputs_total.saturating_add(value);
+            if self.watched.contains(&output.address) {
+                received = received.saturating_add(value);
+                self.unspent
+                    .insert((fragment_id, index as u8), output.value);
+            } else {
+                sent_out = true;
+                counterparties.push(output.address);
+            }
+        }
+
+        if sent == 0 && received == 0 {
+            return None;
+        }
+        let direction = if sent == 0 {
+            TransactionDirection::Incoming
+        } else if sent_out {
+            TransactionDirection::Outgoing
+        } else {
+            TransactionDirection::SelfTransfer
+        };
+        Some(TransactionRecord {
+            fragment_id,
+            date,
+            direction,
+            received: value::Value(received),
+            sent: value::Value(sent),
+            fee: value::Value(inputs_total.saturating_sub(outputs_total)),
+            counterparties,
+            certificate_kind,
+        })
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl HistoryBuilder {
+    pub fn new(discrimination: AddressDiscrimination) -> HistoryBuilder {
+        HistoryBuilder {
+            discrimination: discrimination.into(),
+            watched: HashSet::new(),
+            accounts: Vec::new(),
+            unspent: HashMap::new(),
+            records: Vec::new(),
+        }
+    }
+
+    /// Only the blocks added after this call are taken into account
+    pub fn watch_address(&mut self, address: &Address) {
+        self.watched.insert(address.0.clone());
+    }
+
+    /// Track the inputs spending from the account, and the outputs sent to
+    /// its address
+    pub fn watch_account(&mut self, account: &Account) {
+        self.accounts.push(account.to_identifier().0);
+        self.watched
+            .insert(account.to_address(self.discrimination.into()).0);
+    }
+
+    /// Add the records of the transactions of the block involving the wallet,
+    /// the block must follow the previous one
+    pub fn add_block(&mut self, block: &Block) -> TransactionRecords {
+        let date = BlockDate::from(block.0.date());
+        let records = block
+            .0
+            .fragments()
+            .filter_map(|fragment| self.record(fragment, date))
+            .collect::<Vec<_>>();
+        self.records.extend(records.iter().cloned());
+        records.into()
+    }
+
+    /// All the records, oldest first
+    pub fn records(&self) -> TransactionRecords {
+        self.records.clone().into()
+    }
+}
@@ -0,0 +1,1319 @@
+//! JSON representation of the domain types
+//!
+//! Every type has `to_json` and `from_json` to convert from and to a JSON
+//! string, and with the javascript bindings `to_js_value` and `from_js_value`
+//! to convert from and to a plain javascript object with the same schema.
+//! The schema is stable between releases:
+//!
+//! * keys are bech32 strings (`ed25519_pk1...`, `kes25519-12-pk1...`, `vrf_pk1...`)
+//! * hashes, identifiers and signatures are hexadecimal strings
+//! * addresses are bech32 strings with the `ca` prefix on the production
+//!   network and `ta` on the test networks, legacy addresses are base58 strings
+//! * values, serials and time offsets (in seconds) are decimal strings, as
+//!   they don't fit in a javascript number
+//! * the variants of a type are told apart by their `type` field
+//!
+//! The identifiers (`id`, `sign_data_hash`) are computed on export and
+//! ignored on import. An account identifier (`account`) is the hex of the
+//! public key of a single account or of the identifier of a multisig account.
+//!
+//! ```json
+//! {
+//!   "id": "b0d6...",
+//!   "type": "stake_delegation",
+//!   "sign_data_hash": "57c1...",
+//!   "payload": {
+//!     "type": "stake_delegation",
+//!     "account": "cbc7...",
+//!     "delegation": { "type": "full", "pool_id": "541d..." }
+//!   },
+//!   "inputs": [{ "type": "account", "account": "cbc7...", "value": "1000" }],
+//!   "outputs": [{ "address": "ca1q5nr...", "value": "500" }],
+//!   "witnesses": [{ "type": "account", "signature": "e3d1..." }],
+//!   "payload_auth": { "type": "account_binding", "signature": "0b13..." }
+//! }
+//! ```
+//!
+//! Inputs are either `{"type": "utxo", "transaction_id", "output_index", "value"}`
+//! or `{"type": "account", "account", "value"}`. Witnesses are `utxo` and
+//! `account` with a `signature`, `legacy_utxo` with a `public_key` and a
+//! `signature`, or `multisig` with the encoded witness in `bytes`. The
+//! payload authentication is `account_binding` (`signature`), `pool_owners`
+//! (`signatures`, a list of `{index, signature}`) or `pool_operator`
+//! (`signature`), and is absent for the transactions without payload and the
+//! owner stake delegations.
+//!
+//! The certificates are `stake_delegation`, `owner_stake_delegation`,
+//! `pool_registration`, `pool_retirement` and `pool_update`, the delegation
+//! is `non_delegated`, `full` (`pool_id`) or `ratio` (`parts` and `pools`, a
+//! list of `{pool_id, parts}`).
+//!
+//! The transaction fragments have the fields of the transaction, the legacy
+//! declarations have a list of `{address, value}` in `addresses`. The
+//! initial fragment and the update proposals and votes are only given as
+//! their encoding in `bytes`. A block is its `header` and its `fragments`:
+//! the fields of the header are for reading only, it is imported from its
+//! encoding in `bytes` and must match the fragments.
+
+use crate::{
+    tx, value, Address, Block, Certificate, ErrorKind, Fragment, GenesisPraosLeaderHash, Input,
+    JsChainError, Output, OwnerStakeDelegation, PoolId, PoolRegistration, PoolRetirement,
+    PoolUpdate, StakeDelegation, TaggedTransaction, TimeOffsetSeconds, Transaction, UtxoPointer,
+    Value, Witness,
+};
+use chain::block::{BlockVersion, ContentsBuilder, Header};
+use chain::certificate::{self, PoolPermissions, PoolSignature};
+use chain::fragment::Fragment as ChainFragment;
+use chain::{account, legacy};
+use chain_core::mempack::{ReadBuf, Readable};
+use chain_core::property::{Block as _, Deserialize as _, Fragment as _, Serialize as _};
+use chain_crypto as crypto;
+use chain_impl_mockchain as chain;
+use crypto::bech32::Bech32 as _;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU64;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// maximum number of inputs, outputs or witnesses of a transaction
+const MAX_IOS: usize = 255;
+
+/// Conversion from and to the serde representation of a type
+trait JsonRepr: Sized {
+    type Json: Serialize + DeserializeOwned;
+
+    fn to_json_repr(&self) -> Result<Self::Json, JsChainError>;
+
+    fn from_json_repr(json: Self::Json) -> Result<Self, JsChainError>;
+}
+
+macro_rules! impl_json {
+    ($name:ident) => {
+        #[cfg_attr(feature = "wasm", wasm_bindgen)]
+        impl $name {
+            /// JSON representation, the schema is documented in `src/json.rs`
+            pub fn to_json(&self) -> Result<String, JsChainError> {
+                serde_json::to_string(&self.to_json_repr()?).map_err(|e| {
+                    JsChainError::with_source(ErrorKind::Serialization, "Failed to write JSON", e)
+                })
+            }
+
+            pub fn from_json(json: &str) -> Result<$name, JsChainError> {
+                let json = serde_json::from_str(json).map_err(|e| {
+                    JsChainError::with_source(ErrorKind::Deserialization, "Invalid JSON", e)
+                })?;
+                $name::from_json_repr(json)
+            }
+        }
+
+        #[cfg(feature = "wasm")]
+        #[wasm_bindgen]
+        impl $name {
+            /// Same as `to_json`, as a javascript object instead of a string
+            pub fn to_js_value(&self) -> Result<JsValue, JsChainError> {
+                JsValue::from_serde(&self.to_json_repr()?).map_err(|e| {
+                    JsChainError::with_source(
+                        ErrorKind::Serialization,
+                        "Failed to convert to a javascript object",
+                        e,
+                    )
+                })
+            }
+
+            pub fn from_js_value(value: &JsValue) -> Result<$name, JsChainError> {
+                let json = value.into_serde().map_err(|e| {
+                    JsChainError::with_source(ErrorKind::Deserialization, "Invalid JSON", e)
+                })?;
+                $name::from_json_repr(json)
+            }
+        }
+    };
+}
+
+impl_json!(Address);
+impl_json!(UtxoPointer);
+impl_json!(Input);
+impl_json!(Output);
+impl_json!(Witness);
+impl_json!(Certificate);
+impl_json!(StakeDelegation);
+impl_json!(OwnerStakeDelegation);
+impl_json!(PoolRegistration);
+impl_json!(PoolRetirement);
+impl_json!(PoolUpdate);
+impl_json!(Transaction);
+impl_json!(Fragment);
+impl_json!(Block);
+
+//-----------------------------------//
+//----------- Primitives ------------//
+//-----------------------------------//
+
+fn hex_from_json(what: &str, hex_string: &str) -> Result<Vec<u8>, JsChainError> {
+    hex::decode(hex_string)
+        .map_err(|e| JsChainError::with_source(ErrorKind::Hex, format!("Invalid {}", what), e))
+}
+
+fn hash_bytes_from_json(what: &str, hex_string: &str) -> Result<[u8; 32], JsChainError> {
+    let bytes = hex_from_json(what, hex_string)?;
+    if bytes.len() != 32 {
+        return Err(JsChainError::new(
+            ErrorKind::InvalidLength,
+            format!("Invalid {}: expected 32 bytes, found {}", what, bytes.len()),
+        ));
+    }
+    let mut array = [0u8; 32];
+    array.copy_from_slice(&bytes);
+    Ok(array)
+}
+
+fn value_to_json(value: value::Value) -> String {
+    format!("{}", value)
+}
+
+fn value_from_json(value: &str) -> Result<value::Value, JsChainError> {
+    Value::from_str(value).map(|value| value.0)
+}
+
+fn public_key_to_json<A: crypto::AsymmetricPublicKey>(key: &crypto::PublicKey<A>) -> String {
+    key.to_bech32_str()
+}
+
+fn public_key_from_json<A: crypto::AsymmetricPublicKey>(
+    key: &str,
+) -> Result<crypto::PublicKey<A>, JsChainError> {
+    crypto::PublicKey::try_from_bech32_str(key).map_err(|e| {
+        JsChainError::with_source(ErrorKind::Bech32, "Invalid public key", format!("{:?}", e))
+    })
+}
+
+fn signature_from_json<T, A: crypto::VerificationAlgorithm>(
+    signature: &str,
+) -> Result<crypto::Signature<T, A>, JsChainError> {
+    let bytes = hex_from_json("signature", signature)?;
+    crypto::Signature::from_binary(&bytes).map_err(|e| {
+        JsChainError::with_source(
+            ErrorKind::Deserialization,
+            "Invalid signature",
+            format!("{:?}", e),
+        )
+    })
+}
+
+fn time_offset_to_json(offset: chain_time::timeline::TimeOffsetSeconds) -> String {
+    format!("{}", u64::from(offset))
+}
+
+fn time_offset_from_json(
+    offset: &str,
+) -> Result<chain_time::timeline::TimeOffsetSeconds, JsChainError> {
+    TimeOffsetSeconds::from_string(offset).map(|offset| offset.0)
+}
+
+fn pool_id_from_json(pool_id: &str) -> Result<certificate::PoolId, JsChainError> {
+    PoolId::from_hex(pool_id).map(|pool_id| pool_id.0)
+}
+
+fn account_from_json(account: &str) -> Result<tx::UnspecifiedAccountIdentifier, JsChainError> {
+    hash_bytes_from_json("account identifier", account).map(tx::UnspecifiedAccountIdentifier::from)
+}
+
+//-----------------------------------//
+//------------- Address -------------//
+//-----------------------------------//
+
+fn address_to_json(address: &chain_addr::Address) -> String {
+    let prefix = match address.0 {
+        chain_addr::Discrimination::Production => "ca",
+        chain_addr::Discrimination::Test => "ta",
+    };
+    format!(
+        "{}",
+        chain_addr::AddressReadable::from_address(prefix, address)
+    )
+}
+
+impl JsonRepr for Address {
+    type Json = String;
+
+    fn to_json_repr(&self) -> Result<String, JsChainError> {
+        Ok(address_to_json(&self.0))
+    }
+
+    fn from_json_repr(json: String) -> Result<Address, JsChainError> {
+        Address::from_string(&json)
+    }
+}
+
+//-----------------------------------//
+//--------- Inputs, outputs ---------//
+//-----------------------------------//
+
+#[derive(Serialize, Deserialize)]
+struct UtxoPointerJson {
+    transaction_id: String,
+    output_index: u8,
+    value: String,
+}
+
+impl JsonRepr for UtxoPointer {
+    type Json = UtxoPointerJson;
+
+    fn to_json_repr(&self) -> Result<UtxoPointerJson, JsChainError> {
+        Ok(UtxoPointerJson {
+            transaction_id: format!("{}", self.0.transaction_id),
+            output_index: self.0.output_index,
+            value: value_to_json(self.0.value),
+        })
+    }
+
+    fn from_json_repr(json: UtxoPointerJson) -> Result<UtxoPointer, JsChainError> {
+        Ok(UtxoPointer(tx::UtxoPointer {
+            transaction_id: json.transaction_id.parse().map_err(|e| {
+                JsChainError::with_source(ErrorKind::Hex, "Invalid transaction id", e)
+            })?,
+            output_index: json.output_index,
+            value: value_from_json(&json.value)?,
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum InputJson {
+    Utxo(UtxoPointerJson),
+    Account { account: String, value: String },
+}
+
+impl JsonRepr for Input {
+    type Json = InputJson;
+
+    fn to_json_repr(&self) -> Result<InputJson, JsChainError> {
+        Ok(match self.0.to_enum() {
+            tx::InputEnum::UtxoInput(pointer) => {
+                InputJson::Utxo(UtxoPointer(pointer).to_json_repr()?)
+            }
+            tx::InputEnum::AccountInput(account, value) => InputJson::Account {
+                account: hex::encode(account.as_ref()),
+                value: value_to_json(value),
+            },
+        })
+    }
+
+    fn from_json_repr(json: InputJson) -> Result<Input, JsChainError> {
+        Ok(Input(match json {
+            InputJson::Utxo(pointer) => {
+                tx::Input::from_utxo(UtxoPointer::from_json_repr(pointer)?.0)
+            }
+            InputJson::Account { account, value } => {
+                tx::Input::from_account(account_from_json(&account)?, value_from_json(&value)?)
+            }
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct OutputJson {
+    address: String,
+    value: String,
+}
+
+impl JsonRepr for Output {
+    type Json = OutputJson;
+
+    fn to_json_repr(&self) -> Result<OutputJson, JsChainError> {
+        Ok(OutputJson {
+            address: address_to_json(&self.0.address),
+            value: value_to_json(self.0.value),
+        })
+    }
+
+    fn from_json_repr(json: OutputJson) -> Result<Output, JsChainError> {
+        Ok(Output(tx::Output::from_address(
+            Address::from_string(&json.address)?.0,
+            value_from_json(&json.value)?,
+        )))
+    }
+}
+
+//-----------------------------------//
+//------------ Witnesses ------------//
+//-----------------------------------//
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WitnessJson {
+    Utxo {
+        signature: String,
+    },
+    Account {
+        signature: String,
+    },
+    LegacyUtxo {
+        public_key: String,
+        signature: String,
+    },
+    Multisig {
+        bytes: String,
+    },
+}
+
+impl JsonRepr for Witness {
+    type Json = WitnessJson;
+
+    fn to_json_repr(&self) -> Result<WitnessJson, JsChainError> {
+        Ok(match &self.0 {
+            tx::Witness::Utxo(signature) => WitnessJson::Utxo {
+                signature: hex::encode(signature.as_ref()),
+            },
+            tx::Witness::Account(signature) => WitnessJson::Account {
+                signature: hex::encode(signature.as_ref()),
+            },
+            tx::Witness::OldUtxo(key, signature) => WitnessJson::LegacyUtxo {
+                public_key: public_key_to_json(key),
+                signature: hex::encode(signature.as_ref()),
+            },
+            tx::Witness::Multisig(_) => WitnessJson::Multisig {
+                bytes: hex::encode(self.0.serialize_as_vec().map_err(|e| {
+                    JsChainError::with_source(
+                        ErrorKind::Serialization,
+                        "Failed to serialize the witness",
+                        e,
+                    )
+                })?),
+            },
+        })
+    }
+
+    fn from_json_repr(json: WitnessJson) -> Result<Witness, JsChainError> {
+        Ok(Witness(match json {
+            WitnessJson::Utxo { signature } => tx::Witness::Utxo(signature_from_json(&signature)?),
+            WitnessJson::Account { signature } => {
+                tx::Witness::Account(signature_from_json(&signature)?)
+            }
+            WitnessJson::LegacyUtxo {
+                public_key,
+                signature,
+            } => tx::Witness::OldUtxo(
+                public_key_from_json(&public_key)?,
+                signature_from_json(&signature)?,
+            ),
+            WitnessJson::Multisig { bytes } => {
+                let bytes = hex_from_json("witness", &bytes)?;
+                tx::Witness::read(&mut ReadBuf::from(&bytes)).map_err(|e| {
+                    JsChainError::with_source(ErrorKind::Deserialization, "Invalid witness", e)
+                })?
+            }
+        }))
+    }
+}
+
+//-----------------------------------//
+//---------- Certificates -----------//
+//-----------------------------------//
+
+#[derive(Serialize, Deserialize)]
+struct PoolPartsJson {
+    pool_id: String,
+    parts: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum DelegationJson {
+    NonDelegated,
+    Full {
+        pool_id: String,
+    },
+    Ratio {
+        parts: u8,
+        pools: Vec<PoolPartsJson>,
+    },
+}
+
+fn delegation_to_json(delegation: &account::DelegationType) -> DelegationJson {
+    match delegation {
+        account::DelegationType::NonDelegated => DelegationJson::NonDelegated,
+        account::DelegationType::Full(pool_id) => DelegationJson::Full {
+            pool_id: format!("{}", pool_id),
+        },
+        account::DelegationType::Ratio(ratio) => DelegationJson::Ratio {
+            parts: ratio.parts(),
+            pools: ratio
+                .pools()
+                .iter()
+                .map(|(pool_id, parts)| PoolPartsJson {
+                    pool_id: format!("{}", pool_id),
+                    parts: *parts,
+                })
+                .collect(),
+        },
+    }
+}
+
+fn delegation_from_json(json: DelegationJson) -> Result<account::DelegationType, JsChainError> {
+    Ok(match json {
+        DelegationJson::NonDelegated => account::DelegationType::NonDelegated,
+        DelegationJson::Full { pool_id } => {
+            account::DelegationType::Full(pool_id_from_json(&pool_id)?)
+        }
+        DelegationJson::Ratio { parts, pools } => {
+            let pools = pools
+                .iter()
+                .map(|pool| Ok((pool_id_from_json(&pool.pool_id)?, pool.parts)))
+                .collect::<Result<_, JsChainError>>()?;
+            let ratio = account::DelegationRatio::new(parts, pools).ok_or_else(|| {
+                JsChainError::new(ErrorKind::InvalidArgument, "Invalid delegation ratio")
+            })?;
+            account::DelegationType::Ratio(ratio)
+        }
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+struct StakeDelegationJson {
+    account: String,
+    delegation: DelegationJson,
+}
+
+impl JsonRepr for StakeDelegation {
+    type Json = StakeDelegationJson;
+
+    fn to_json_repr(&self) -> Result<StakeDelegationJson, JsChainError> {
+        Ok(StakeDelegationJson {
+            account: hex::encode(self.0.account_id.as_ref()),
+            delegation: delegation_to_json(&self.0.delegation),
+        })
+    }
+
+    fn from_json_repr(json: StakeDelegationJson) -> Result<StakeDelegation, JsChainError> {
+        Ok(StakeDelegation(certificate::StakeDelegation {
+            account_id: account_from_json(&json.account)?,
+            delegation: delegation_from_json(json.delegation)?,
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct OwnerStakeDelegationJson {
+    delegation: DelegationJson,
+}
+
+impl JsonRepr for OwnerStakeDelegation {
+    type Json = OwnerStakeDelegationJson;
+
+    fn to_json_repr(&self) -> Result<OwnerStakeDelegationJson, JsChainError> {
+        Ok(OwnerStakeDelegationJson {
+            delegation: delegation_to_json(&self.0.delegation),
+        })
+    }
+
+    fn from_json_repr(
+        json: OwnerStakeDelegationJson,
+    ) -> Result<OwnerStakeDelegation, JsChainError> {
+        Ok(OwnerStakeDelegation(certificate::OwnerStakeDelegation {
+            delegation: delegation_from_json(json.delegation)?,
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TaxTypeJson {
+    fixed: String,
+    ratio_numerator: String,
+    ratio_denominator: String,
+    max_limit: Option<String>,
+}
+
+fn non_zero_from_json(what: &str, number: &str) -> Result<NonZeroU64, JsChainError> {
+    NonZeroU64::new(*value_from_json(number)?.as_ref()).ok_or_else(|| {
+        JsChainError::new(
+            ErrorKind::InvalidValue,
+            format!("The {} can't be zero", what),
+        )
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RewardAccountJson {
+    Single { public_key: String },
+    Multi { id: String },
+}
+
+#[derive(Serialize, Deserialize)]
+struct PoolRegistrationJson {
+    #[serde(skip_deserializing)]
+    id: String,
+    serial: String,
+    start_validity: String,
+    management_threshold: u8,
+    owners: Vec<String>,
+    operators: Vec<String>,
+    rewards: TaxTypeJson,
+    reward_account: Option<RewardAccountJson>,
+    kes_public_key: String,
+    vrf_public_key: String,
+}
+
+impl JsonRepr for PoolRegistration {
+    type Json = PoolRegistrationJson;
+
+    fn to_json_repr(&self) -> Result<PoolRegistrationJson, JsChainError> {
+        let registration = &self.0;
+        Ok(PoolRegistrationJson {
+            id: format!("{}", registration.to_id()),
+            serial: format!("{}", registration.serial),
+            start_validity: time_offset_to_json(registration.start_validity),
+            management_threshold: registration.permissions.management_threshold(),
+            owners: registration.owners.iter().map(public_key_to_json).collect(),
+            operators: registration
+                .operators
+                .iter()
+                .map(public_key_to_json)
+                .collect(),
+            rewards: TaxTypeJson {
+                fixed: value_to_json(registration.rewards.fixed),
+                ratio_numerator: format!("{}", registration.rewards.ratio.numerator),
+                ratio_denominator: format!("{}", registration.rewards.ratio.denominator),
+                max_limit: registration
+                    .rewards
+                    .max_limit
+                    .map(|limit| format!("{}", limit)),
+            },
+            reward_account: registration
+                .reward_account
+                .as_ref()
+                .map(|account| match account {
+                    tx::AccountIdentifier::Single(id) => {
+                        let key: crypto::PublicKey<crypto::Ed25519> = id.clone().into();
+                        RewardAccountJson::Single {
+                            public_key: public_key_to_json(&key),
+                        }
+                    }
+                    tx::AccountIdentifier::Multi(id) => RewardAccountJson::Multi {
+                        id: hex::encode(id.as_ref()),
+                    },
+                }),
+            kes_public_key: public_key_to_json(&registration.keys.kes_public_key),
+            vrf_public_key: public_key_to_json(&registration.keys.vrf_public_key),
+        })
+    }
+
+    fn from_json_repr(json: PoolRegistrationJson) -> Result<PoolRegistration, JsChainError> {
+        let keys = |keys: &[String]| {
+            keys.iter()
+                .map(|key| public_key_from_json(key))
+                .collect::<Result<Vec<_>, JsChainError>>()
+        };
+        let reward_account = match json.reward_account {
+            None => None,
+            Some(RewardAccountJson::Single { public_key }) => Some(tx::AccountIdentifier::Single(
+                public_key_from_json::<crypto::Ed25519>(&public_key)?.into(),
+            )),
+            Some(RewardAccountJson::Multi { id }) => Some(tx::AccountIdentifier::Multi(
+                hash_bytes_from_json("multisig account identifier", &id)?.into(),
+            )),
+        };
+        let owners = keys(&json.owners)?;
+        crate::check_management_threshold(json.management_threshold, owners.len())?;
+        Ok(PoolRegistration(certificate::PoolRegistration {
+            serial: json.serial.parse().map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::InvalidValue,
+                    "Invalid serial",
+                    format!("{:?}", e),
+                )
+            })?,
+            owners,
+            operators: keys(&json.operators)?,
+            permissions: PoolPermissions::new(json.management_threshold),
+            start_validity: time_offset_from_json(&json.start_validity)?,
+            rewards: chain::rewards::TaxType {
+                fixed: value_from_json(&json.rewards.fixed)?,
+                ratio: chain::rewards::Ratio {
+                    numerator: *value_from_json(&json.rewards.ratio_numerator)?.as_ref(),
+                    denominator: non_zero_from_json(
+                        "ratio denominator",
+                        &json.rewards.ratio_denominator,
+                    )?,
+                },
+                max_limit: json
+                    .rewards
+                    .max_limit
+                    .map(|limit| non_zero_from_json("maximum limit", &limit))
+                    .transpose()?,
+            },
+            reward_account,
+            keys: chain::leadership::genesis::GenesisPraosLeader {
+                kes_public_key: public_key_from_json(&json.kes_public_key)?,
+                vrf_public_key: public_key_from_json(&json.vrf_public_key)?,
+            },
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PoolRetirementJson {
+    pool_id: String,
+    retirement_time: String,
+}
+
+impl JsonRepr for PoolRetirement {
+    type Json = PoolRetirementJson;
+
+    fn to_json_repr(&self) -> Result<PoolRetirementJson, JsChainError> {
+        Ok(PoolRetirementJson {
+            pool_id: format!("{}", self.0.pool_id),
+            retirement_time: time_offset_to_json(self.0.retirement_time),
+        })
+    }
+
+    fn from_json_repr(json: PoolRetirementJson) -> Result<PoolRetirement, JsChainError> {
+        Ok(PoolRetirement(certificate::PoolRetirement {
+            pool_id: pool_id_from_json(&json.pool_id)?,
+            retirement_time: time_offset_from_json(&json.retirement_time)?,
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PoolUpdateJson {
+    pool_id: String,
+    start_validity: String,
+    previous_keys: String,
+    kes_public_key: String,
+    vrf_public_key: String,
+}
+
+impl JsonRepr for PoolUpdate {
+    type Json = PoolUpdateJson;
+
+    fn to_json_repr(&self) -> Result<PoolUpdateJson, JsChainError> {
+        Ok(PoolUpdateJson {
+            pool_id: format!("{}", self.0.pool_id),
+            start_validity: time_offset_to_json(self.0.start_validity),
+            previous_keys: format!("{}", self.0.previous_keys),
+            kes_public_key: public_key_to_json(&self.0.updated_keys.kes_public_key),
+            vrf_public_key: public_key_to_json(&self.0.updated_keys.vrf_public_key),
+        })
+    }
+
+    fn from_json_repr(json: PoolUpdateJson) -> Result<PoolUpdate, JsChainError> {
+        Ok(PoolUpdate(certificate::PoolUpdate {
+            pool_id: pool_id_from_json(&json.pool_id)?,
+            start_validity: time_offset_from_json(&json.start_validity)?,
+            previous_keys: GenesisPraosLeaderHash::from_hex(&json.previous_keys)?.0,
+            updated_keys: chain::leadership::genesis::GenesisPraosLeader {
+                kes_public_key: public_key_from_json(&json.kes_public_key)?,
+                vrf_public_key: public_key_from_json(&json.vrf_public_key)?,
+            },
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum CertificateJson {
+    StakeDelegation(StakeDelegationJson),
+    OwnerStakeDelegation(OwnerStakeDelegationJson),
+    PoolRegistration(PoolRegistrationJson),
+    PoolRetirement(PoolRetirementJson),
+    PoolUpdate(PoolUpdateJson),
+}
+
+impl JsonRepr for Certificate {
+    type Json = CertificateJson;
+
+    fn to_json_repr(&self) -> Result<CertificateJson, JsChainError> {
+        use certificate::Certificate as C;
+        Ok(match &self.0 {
+            C::StakeDelegation(c) => {
+                CertificateJson::StakeDelegation(StakeDelegation(c.clone()).to_json_repr()?)
+            }
+            C::OwnerStakeDelegation(c) => CertificateJson::OwnerStakeDelegation(
+                OwnerStakeDelegation(c.clone()).to_json_repr()?,
+            ),
+            C::PoolRegistration(c) => {
+                CertificateJson::PoolRegistration(PoolRegistration(c.clone()).to_json_repr()?)
+            }
+            C::PoolRetirement(c) => {
+                CertificateJson::PoolRetirement(PoolRetirement(c.clone()).to_json_repr()?)
+            }
+            C::PoolUpdate(c) => CertificateJson::PoolUpdate(PoolUpdate(c.clone()).to_json_repr()?),
+        })
+    }
+
+    fn from_json_repr(json: CertificateJson) -> Result<Certificate, JsChainError> {
+        use certificate::Certificate as C;
+        Ok(Certificate(match json {
+            CertificateJson::StakeDelegation(c) => {
+                C::StakeDelegation(StakeDelegation::from_json_repr(c)?.0)
+            }
+            CertificateJson::OwnerStakeDelegation(c) => {
+                C::OwnerStakeDelegation(OwnerStakeDelegation::from_json_repr(c)?.0)
+            }
+            CertificateJson::PoolRegistration(c) => {
+                C::PoolRegistration(PoolRegistration::from_json_repr(c)?.0)
+            }
+            CertificateJson::PoolRetirement(c) => {
+                C::PoolRetirement(PoolRetirement::from_json_repr(c)?.0)
+            }
+            CertificateJson::PoolUpdate(c) => C::PoolUpdate(PoolUpdate::from_json_repr(c)?.0),
+        }))
+    }
+}
+
+//-----------------------------------//
+//----------- Transaction -----------//
+//-----------------------------------//
+
+#[derive(Serialize, Deserialize)]
+struct IndexedSignatureJson {
+    index: u8,
+    signature: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PayloadAuthJson {
+    AccountBinding {
+        signature: String,
+    },
+    PoolOwners {
+        signatures: Vec<IndexedSignatureJson>,
+    },
+    PoolOperator {
+        signature: String,
+    },
+}
+
+fn account_binding_signature_to_json(signature: &tx::SingleAccountBindingSignature) -> String {
+    hex::encode(signature.as_ref())
+}
+
+fn account_binding_signature_from_json(
+    signature: &str,
+) -> Result<tx::SingleAccountBindingSignature, JsChainError> {
+    let bytes = hex_from_json("signature", signature)?;
+    tx::SingleAccountBindingSignature::read(&mut ReadBuf::from(&bytes))
+        .map_err(|e| JsChainError::with_source(ErrorKind::Deserialization, "Invalid signature", e))
+}
+
+fn pool_signature_to_json(signature: &PoolSignature) -> PayloadAuthJson {
+    match signature {
+        PoolSignature::Owners(owners) => PayloadAuthJson::PoolOwners {
+            signatures: owners
+                .signatures
+                .iter()
+                .map(|(index, signature)| IndexedSignatureJson {
+                    index: *index,
+                    signature: account_binding_signature_to_json(signature),
+                })
+                .collect(),
+        },
+        PoolSignature::Operator(signature) => PayloadAuthJson::PoolOperator {
+            signature: account_binding_signature_to_json(signature),
+        },
+    }
+}
+
+fn payload_auth_to_json(
+    transaction: &TaggedTransaction,
+) -> Result<Option<PayloadAuthJson>, JsChainError> {
+    use TaggedTransaction as T;
+    Ok(match transaction {
+        T::NoExtra(_) | T::OwnerStakeDelegation(_) => None,
+        T::StakeDelegation(tx) => match tx.as_slice().payload_auth().into_payload_auth() {
+            tx::AccountBindingSignature::Single(signature) => {
+                Some(PayloadAuthJson::AccountBinding {
+                    signature: account_binding_signature_to_json(&signature),
+                })
+            }
+            tx::AccountBindingSignature::Multi(_) => {
+                return Err(JsChainError::new(
+                    ErrorKind::Serialization,
+                    "Multisig account binding signatures are not supported",
+                ))
+            }
+        },
+        T::PoolRegistration(tx) => Some(pool_signature_to_json(
+            &tx.as_slice().payload_auth().into_payload_auth(),
+        )),
+        T::PoolRetirement(tx) => Some(pool_signature_to_json(
+            &tx.as_slice().payload_auth().into_payload_auth(),
+        )),
+        T::PoolUpdate(tx) => Some(pool_signature_to_json(
+            &tx.as_slice().payload_auth().into_payload_auth(),
+        )),
+    })
+}
+
+fn no_payload_auth_from_json(auth: Option<PayloadAuthJson>) -> Result<(), JsChainError> {
+    match auth {
+        None => Ok(()),
+        Some(_) => Err(JsChainError::new(
+            ErrorKind::WrongPayloadAuth,
+            "The transaction payload doesn't take an authentication",
+        )),
+    }
+}
+
+fn account_binding_from_json(
+    auth: Option<PayloadAuthJson>,
+) -> Result<tx::AccountBindingSignature, JsChainError> {
+    match auth {
+        Some(PayloadAuthJson::AccountBinding { signature }) => Ok(
+            tx::AccountBindingSignature::Single(account_binding_signature_from_json(&signature)?),
+        ),
+        _ => Err(JsChainError::new(
+            ErrorKind::WrongPayloadAuth,
+            "A stake delegation is authenticated by an account binding signature",
+        )),
+    }
+}
+
+fn pool_signature_from_json(auth: Option<PayloadAuthJson>) -> Result<PoolSignature, JsChainError> {
+    match auth {
+        Some(PayloadAuthJson::PoolOwners { signatures }) => signatures
+            .iter()
+            .map(|indexed| {
+                Ok((
+                    indexed.index,
+                    account_binding_signature_from_json(&indexed.signature)?,
+                ))
+            })
+            .collect::<Result<_, JsChainError>>()
+            .map(|signatures| PoolSignature::Owners(certificate::PoolOwnersSigned { signatures })),
+        Some(PayloadAuthJson::PoolOperator { signature }) => Ok(PoolSignature::Operator(
+            account_binding_signature_from_json(&signature)?,
+        )),
+        _ => Err(JsChainError::new(
+            ErrorKind::WrongPayloadAuth,
+            "A pool certificate is authenticated by pool owners or operator signatures",
+        )),
+    }
+}
+
+fn build_transaction<P: tx::Payload>(
+    builder: tx::TxBuilderState<tx::SetIOs<P>>,
+    inputs: &[tx::Input],
+    outputs: &[tx::Output<chain_addr::Address>],
+    witnesses: &[tx::Witness],
+    auth: &P::Auth,
+) -> tx::Transaction<P> {
+    builder
+        .set_ios(inputs, outputs)
+        .set_witnesses(witnesses)
+        .set_payload_auth(auth)
+}
+
+#[derive(Serialize, Deserialize)]
+struct TransactionJson {
+    #[serde(skip_deserializing)]
+    sign_data_hash: String,
+    payload: Option<CertificateJson>,
+    inputs: Vec<InputJson>,
+    outputs: Vec<OutputJson>,
+    witnesses: Vec<WitnessJson>,
+    payload_auth: Option<PayloadAuthJson>,
+}
+
+impl JsonRepr for Transaction {
+    type Json = TransactionJson;
+
+    fn to_json_repr(&self) -> Result<TransactionJson, JsChainError> {
+        Ok(TransactionJson {
+            sign_data_hash: format!("{}", self.id().0),
+            payload: self
+                .certificate()
+                .map(|certificate| certificate.to_json_repr())
+                .transpose()?,
+            inputs: self
+                .0
+                .inputs()
+                .into_iter()
+                .map(|input| Input(input).to_json_repr())
+                .collect::<Result<_, _>>()?,
+            outputs: self
+                .0
+                .outputs()
+                .into_iter()
+                .map(|output| Output(output).to_json_repr())
+                .collect::<Result<_, _>>()?,
+            witnesses: self
+                .witnesses()
+                .0
+                .iter()
+                .map(|witness| witness.to_json_repr())
+                .collect::<Result<_, _>>()?,
+            payload_auth: payload_auth_to_json(&self.0)?,
+        })
+    }
+
+    fn from_json_repr(json: TransactionJson) -> Result<Transaction, JsChainError> {
+        use certificate::Certificate as C;
+        use TaggedTransaction as T;
+
+        if json.inputs.len() > MAX_IOS
+            || json.outputs.len() > MAX_IOS
+            || json.witnesses.len() > MAX_IOS
+        {
+            return Err(JsChainError::new(
+                ErrorKind::TransactionBuilder,
+                format!(
+                    "A transaction has at most {} inputs, outputs and witnesses",
+                    MAX_IOS
+                ),
+            ));
+        }
+        let inputs = json
+            .inputs
+            .into_iter()
+            .map(|input| Input::from_json_repr(input).map(|input| input.0))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = json
+            .outputs
+            .into_iter()
+            .map(|output| Output::from_json_repr(output).map(|output| output.0))
+            .collect::<Result<Vec<_>, _>>()?;
+        let witnesses = json
+            .witnesses
+            .into_iter()
+            .map(|witness| Witness::from_json_repr(witness).map(|witness| witness.0))
+            .collect::<Result<Vec<_>, _>>()?;
+        let payload = json
+            .payload
+            .map(|payload| Certificate::from_json_repr(payload).map(|certificate| certificate.0))
+            .transpose()?;
+        let auth = json.payload_auth;
+        let builder = tx::TxBuilder::new();
+
+        let transaction = match payload {
+            None => {
+                no_payload_auth_from_json(auth)?;
+                T::NoExtra(build_transaction(
+                    builder.set_nopayload(),
+                    &inputs,
+                    &outputs,
+                    &witnesses,
+                    &(),
+                ))
+            }
+            Some(C::OwnerStakeDelegation(c)) => {
+                no_payload_auth_from_json(auth)?;
+                T::OwnerStakeDelegation(build_transaction(
+                    builder.set_payload(&c),
+                    &inputs,
+                    &outputs,
+                    &witnesses,
+                    &(),
+                ))
+            }
+            Some(C::StakeDelegation(c)) => T::StakeDelegation(build_transaction(
+                builder.set_payload(&c),
+                &inputs,
+                &outputs,
+                &witnesses,
+                &account_binding_from_json(auth)?,
+            )),
+            Some(C::PoolRegistration(c)) => T::PoolRegistration(build_transaction(
+                builder.set_payload(&c),
+                &inputs,
+                &outputs,
+                &witnesses,
+                &pool_signature_from_json(auth)?,
+            )),
+            Some(C::PoolRetirement(c)) => T::PoolRetirement(build_transaction(
+                builder.set_payload(&c),
+                &inputs,
+                &outputs,
+                &witnesses,
+                &pool_signature_from_json(auth)?,
+            )),
+            Some(C::PoolUpdate(c)) => T::PoolUpdate(build_transaction(
+                builder.set_payload(&c),
+                &inputs,
+                &outputs,
+                &witnesses,
+                &pool_signature_from_json(auth)?,
+            )),
+        };
+        Ok(Transaction(transaction))
+    }
+}
+
+//-----------------------------------//
+//------------ Fragment -------------//
+//-----------------------------------//
+
+#[derive(Serialize, Deserialize)]
+struct LegacyFundJson {
+    address: String,
+    value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum FragmentContentJson {
+    Initial { bytes: String },
+    OldUtxoDeclaration { addresses: Vec<LegacyFundJson> },
+    Transaction(TransactionJson),
+    OwnerStakeDelegation(TransactionJson),
+    StakeDelegation(TransactionJson),
+    PoolRegistration(TransactionJson),
+    PoolRetirement(TransactionJson),
+    PoolUpdate(TransactionJson),
+    UpdateProposal { bytes: String },
+    UpdateVote { bytes: String },
+}
+
+#[derive(Serialize, Deserialize)]
+struct FragmentJson {
+    #[serde(skip_deserializing)]
+    id: String,
+    #[serde(flatten)]
+    content: FragmentContentJson,
+}
+
+/// fragments without a JSON representation of their content
+fn opaque_fragment_from_json(
+    bytes: &str,
+    is_expected_type: fn(&ChainFragment) -> bool,
+) -> Result<Fragment, JsChainError> {
+    let fragment = Fragment::from_bytes(&hex_from_json("fragment", bytes)?)?;
+    if !is_expected_type(&fragment.0) {
+        return Err(JsChainError::new(
+            ErrorKind::WrongType,
+            "The bytes don't match the type of the fragment",
+        ));
+    }
+    Ok(fragment)
+}
+
+impl JsonRepr for Fragment {
+    type Json = FragmentJson;
+
+    fn to_json_repr(&self) -> Result<FragmentJson, JsChainError> {
+        use FragmentContentJson as J;
+        let transaction = || self.get_transaction()?.to_json_repr();
+        let bytes = || self.as_bytes().map(hex::encode);
+        let content = match &self.0 {
+            ChainFragment::Initial(_) => J::Initial { bytes: bytes()? },
+            ChainFragment::OldUtxoDeclaration(declaration) => J::OldUtxoDeclaration {
+                addresses: declaration
+                    .addrs
+                    .iter()
+                    .map(|(address, value)| LegacyFundJson {
+                        address: format!("{}", address),
+                        value: value_to_json(*value),
+                    })
+                    .collect(),
+            },
+            ChainFragment::Transaction(_) => J::Transaction(transaction()?),
+            ChainFragment::OwnerStakeDelegation(_) => J::OwnerStakeDelegation(transaction()?),
+            ChainFragment::StakeDelegation(_) => J::StakeDelegation(transaction()?),
+            ChainFragment::PoolRegistration(_) => J::PoolRegistration(transaction()?),
+            ChainFragment::PoolRetirement(_) => J::PoolRetirement(transaction()?),
+            ChainFragment::PoolUpdate(_) => J::PoolUpdate(transaction()?),
+            ChainFragment::UpdateProposal(_) => J::UpdateProposal { bytes: bytes()? },
+            ChainFragment::UpdateVote(_) => J::UpdateVote { bytes: bytes()? },
+        };
+        Ok(FragmentJson {
+            id: format!("{}", self.0.id()),
+            content,
+        })
+    }
+
+    fn from_json_repr(json: FragmentJson) -> Result<Fragment, JsChainError> {
+        use FragmentContentJson as J;
+        let transaction =
+            |json: TransactionJson, is_expected_type: fn(&TaggedTransaction) -> bool| {
+                let transaction = Transaction::from_json_repr(json)?;
+                if !is_expected_type(&transaction.0) {
+                    return Err(JsChainError::new(
+                        ErrorKind::WrongType,
+                        "The payload doesn't match the type of the fragment",
+                    ));
+                }
+                Ok(Fragment::from_transaction(&transaction))
+            };
+        match json.content {
+            J::Initial { bytes } => opaque_fragment_from_json(&bytes, |fragment| match fragment {
+                ChainFragment::Initial(_) => true,
+                _ => false,
+            }),
+            J::OldUtxoDeclaration { addresses } => {
+                if addresses.len() > MAX_IOS {
+                    return Err(JsChainError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("A declaration has at most {} addresses", MAX_IOS),
+                    ));
+                }
+                let addrs = addresses
+                    .iter()
+                    .map(|fund| {
+                        let address = fund.address.parse::<legacy::OldAddress>().map_err(|e| {
+                            JsChainError::with_source(
+                                ErrorKind::InvalidAddress,
+                                "Invalid legacy address",
+                                e,
+                            )
+                        })?;
+                        Ok((address, value_from_json(&fund.value)?))
+                    })
+                    .collect::<Result<_, JsChainError>>()?;
+                Ok(Fragment(ChainFragment::OldUtxoDeclaration(
+                    legacy::UtxoDeclaration { addrs },
+                )))
+            }
+            J::Transaction(json) => transaction(json, |tagged| match tagged {
+                TaggedTransaction::NoExtra(_) => true,
+                _ => false,
+            }),
+            J::OwnerStakeDelegation(json) => transaction(json, |tagged| match tagged {
+                TaggedTransaction::OwnerStakeDelegation(_) => true,
+                _ => false,
+            }),
+            J::StakeDelegation(json) => transaction(json, |tagged| match tagged {
+                TaggedTransaction::StakeDelegation(_) => true,
+                _ => false,
+            }),
+            J::PoolRegistration(json) => transaction(json, |tagged| match tagged {
+                TaggedTransaction::PoolRegistration(_) => true,
+                _ => false,
+            }),
+            J::PoolRetirement(json) => transaction(json, |tagged| match tagged {
+                TaggedTransaction::PoolRetirement(_) => true,
+                _ => false,
+            }),
+            J::PoolUpdate(json) => transaction(json, |tagged| match tagged {
+                TaggedTransaction::PoolUpdate(_) => true,
+                _ => false,
+            }),
+            J::UpdateProposal { bytes } => {
+                opaque_fragment_from_json(&bytes, |fragment| match fragment {
+                    ChainFragment::UpdateProposal(_) => true,
+                    _ => false,
+                })
+            }
+            J::UpdateVote { bytes } => {
+                opaque_fragment_from_json(&bytes, |fragment| match fragment {
+                    ChainFragment::UpdateVote(_) => true,
+                    _ => false,
+                })
+            }
+        }
+    }
+}
+
+//-----------------------------------//
+//-------------- Block --------------//
+//-----------------------------------//
+
+#[derive(Default, Serialize, Deserialize)]
+struct BlockDateJson {
+    epoch: u32,
+    slot: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HeaderJson {
+    #[serde(skip_deserializing)]
+    id: String,
+    #[serde(skip_deserializing)]
+    parent_id: String,
+    #[serde(skip_deserializing)]
+    version: String,
+    #[serde(skip_deserializing)]
+    date: BlockDateJson,
+    #[serde(skip_deserializing)]
+    chain_length: u32,
+    #[serde(skip_deserializing)]
+    content_hash: String,
+    #[serde(skip_deserializing)]
+    content_size: u32,
+    /// public key of the leader of a BFT block
+    #[serde(skip_deserializing)]
+    bft_leader: Option<String>,
+    /// stake pool of a genesis praos block
+    #[serde(skip_deserializing)]
+    pool_id: Option<String>,
+    bytes: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BlockJson {
+    header: HeaderJson,
+    fragments: Vec<FragmentJson>,
+}
+
+impl JsonRepr for Block {
+    type Json = BlockJson;
+
+    fn to_json_repr(&self) -> Result<BlockJson, JsChainError> {
+        let header = &self.0.header;
+        let version = match header.block_version() {
+            BlockVersion::Genesis => "genesis",
+            BlockVersion::Ed25519Signed => "bft",
+            BlockVersion::KesVrfproof => "genesis_praos",
+        };
+        let date = self.0.date();
+        let header_bytes = header.serialize_as_vec().map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::Serialization,
+                "Failed to serialize the header",
+                e,
+            )
+        })?;
+        Ok(BlockJson {
+            header: HeaderJson {
+                id: format!("{}", self.0.id()),
+                parent_id: format!("{}", self.0.parent_id()),
+                version: version.to_string(),
+                date: BlockDateJson {
+                    epoch: date.epoch,
+                    slot: date.slot_id,
+                },
+                chain_length: u32::from(self.0.chain_length()),
+                content_hash: format!("{}", header.block_content_hash()),
+                content_size: header.block_content_size(),
+                bft_leader: header
+                    .get_bft_leader_id()
+                    .map(|leader| public_key_to_json(leader.as_public_key())),
+                pool_id: header
+                    .get_stakepool_id()
+                    .map(|pool_id| format!("{}", pool_id)),
+                bytes: hex::encode(header_bytes),
+            },
+            fragments: self
+                .0
+                .fragments()
+                .map(|fragment| Fragment(fragment.clone()).to_json_repr())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn from_json_repr(json: BlockJson) -> Result<Block, JsChainError> {
+        let header_bytes = hex_from_json("header", &json.header.bytes)?;
+        let header = Header::deserialize(header_bytes.as_slice()).map_err(|e| {
+            JsChainError::with_source(ErrorKind::Deserialization, "Invalid header", e)
+        })?;
+        let mut contents = ContentsBuilder::new();
+        for fragment in json.fragments {
+            contents.push(Fragment::from_json_repr(fragment)?.0);
+        }
+        let contents: chain::block::Contents = contents.into();
+        let (content_hash, content_size) = contents.compute_hash_size();
+        if content_hash != header.block_content_hash()
+            || content_size != header.block_content_size()
+        {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidBlock,
+                "The fragments don't match the content hash of the header",
+            ));
+        }
+        Ok(Block(chain::block::Block { header, contents }))
+    }
+}
@@ -0,0 +1,161 @@
+//! Leader schedule of a stake pool
+//!
+//! With genesis praos, a pool leads a slot when the output of its VRF key on
+//! the epoch nonce and the slot is under a threshold depending on the
+//! relative stake of the pool and the active slots coefficient. The
+//! evaluation only needs the secret VRF key, so the operator can know its
+//! slots for the whole epoch as soon as the nonce and the stake distribution
+//! of the epoch are known.
+
+use crate::{BlockDate, ErrorKind, JsChainError, Value, VrfSecretKey};
+use chain::leadership::genesis::{ActiveSlotsCoeff, Nonce, PercentStake, VrfEvaluator};
+use chain::milli::Milli;
+use chain::stake::Stake;
+use chain_core::mempack::{ReadBuf, Readable};
+use chain_impl_mockchain as chain;
+use std::convert::TryFrom;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Slot led by the pool, with the VRF proof to put in the block header
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct LeaderSlot {
+    date: BlockDate,
+    proof: Vec<u8>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl LeaderSlot {
+    pub fn date(&self) -> BlockDate {
+        self.date
+    }
+
+    pub fn proof(&self) -> Vec<u8> {
+        self.proof.clone()
+    }
+}
+
+crate::impl_collection!(LeaderSlots, LeaderSlot);
+
+/// Compute the slots a stake pool leads
+///
+/// ```javascript
+/// const calculator = LeaderScheduleCalculator.new(vrf_secret_key, 100, settings.slots_per_epoch());
+/// const slots = calculator.schedule(epoch, epoch_nonce, pool_stake, total_stake);
+/// for (let i = 0; i &lt; slots.size(); i++) {
+///     console.log(slots.get(i).date().slot());
+/// }
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct LeaderScheduleCalculator {
+    vrf_secret_key: VrfSecretKey,
+    active_slots_coeff: ActiveSlotsCoeff,
+    slots_per_epoch: u32,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl LeaderScheduleCalculator {
+    /// The active slots coefficient is in thousandths (e.g. 100 for 0.1)
+    pub fn new(
+        vrf_secret_key: &VrfSecretKey,
+        active_slots_coeff: u32,
+        slots_per_epoch: u32,
+    ) -> Result<LeaderScheduleCalculator, JsChainError> {
+        if active_slots_coeff == 0 || active_slots_coeff > 1000 {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "Active slots coefficient must be between 1 and 1000 thousandths",
+            ));
+        }
+        let active_slots_coeff = ActiveSlotsCoeff::try_from(Milli::from_millis(u64::from(
+            active_slots_coeff,
+        )))
+        .map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::InvalidArgument,
+                "Invalid active slots coefficient",
+                format!("{:?}", e),
+            )
+        })?;
+        Ok(LeaderScheduleCalculator {
+            vrf_secret_key: vrf_secret_key.clone(),
+            active_slots_coeff,
+            slots_per_epoch,
+        })
+    }
+
+    /// The proof if the pool leads the slot, null otherwise
+    pub fn leads(
+        &self,
+        date: &BlockDate,
+        epoch_nonce: &[u8],
+        pool_stake: &Value,
+        total_stake: &Value,
+    ) -> Result<Option<LeaderSlot>, JsChainError> {
+        let nonce = read_nonce(epoch_nonce)?;
+        let stake = percent_stake(pool_stake, total_stake)?;
+        Ok(self.evaluate(date, &nonce, stake))
+    }
+
+    /// Slots of the epoch led by the pool, the nonce and the stake
+    /// distribution are the ones of the epoch
+    pub fn schedule(
+        &self,
+        epoch: u32,
+        epoch_nonce: &[u8],
+        pool_stake: &Value,
+        total_stake: &Value,
+    ) -> Result<LeaderSlots, JsChainError> {
+        let nonce = read_nonce(epoch_nonce)?;
+        let stake = percent_stake(pool_stake, total_stake)?;
+        Ok((0..self.slots_per_epoch)
+            .filter_map(|slot| self.evaluate(&BlockDate::new(epoch, slot), &nonce, stake))
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+impl LeaderScheduleCalculator {
+    fn evaluate(&self, date: &BlockDate, nonce: &Nonce, stake: (u64, u64)) -> Option<LeaderSlot> {
+        let evaluator = VrfEvaluator {
+            stake: PercentStake {
+                stake: Stake(stake.0),
+                total: Stake(stake.1),
+            },
+            nonce,
+            slot_id: date.slot(),
+            active_slots_coeff: self.active_slots_coeff,
+        };
+        evaluator
+            .evaluate(&self.vrf_secret_key.0)
+            .map(|witness| LeaderSlot {
+                date: *date,
+                proof: witness.to_bytes().to_vec(),
+            })
+    }
+}
+
+fn read_nonce(bytes: &[u8]) -> Result<Nonce, JsChainError> {
+    if bytes.len() != 32 {
+        return Err(JsChainError::new(
+            ErrorKind::InvalidLength,
+            format!("Invalid nonce length. Found {}, expected: 32", bytes.len()),
+        ));
+    }
+    Nonce::read(&mut ReadBuf::from(bytes))
+        .map_err(|e| JsChainError::with_source(ErrorKind::Deserialization, "Invalid nonce", e))
+}
+
+/// stake of the pool and total stake
+fn percent_stake(pool_stake: &Value, total_stake: &Value) -> Result<(u64, u64), JsChainError> {
+    let stake = *pool_stake.0.as_ref();
+    let total = *total_stake.0.as_ref();
+    if total == 0 || stake > total {
+        return Err(JsChainError::new(
+            ErrorKind::InvalidValue,
+            "The stake of the pool must be at most the total stake, which can't be zero",
+        ));
+    }
+    Ok((stake, total))
+}
@@ -0,0 +1,292 @@
+//! Offline validation of fragments
+//!
+//! A copy of the node's ledger is rebuilt from block0 and the following blocks,
+//! so fragments can be checked locally before being sent to a node.
+
+use crate::{
+    account, tx, value, Account, Block, BlockId, ErrorKind, Fragment, JsChainError,
+    SpendingCounter, Value,
+};
+use chain::block::HeaderContentEvalContext;
+use chain::key;
+use chain::ledger::{self, Ledger, LedgerParameters};
+use chain_core::property::Block as _;
+use chain_crypto as crypto;
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// how far from the expected spending counter we look for the one an invalid
+/// account witness was made with
+const SPENDING_COUNTER_SEARCH_WINDOW: u32 = 32;
+
+/// Reason a fragment would be rejected by the ledger
+///
+/// * InvalidWitness: a utxo or legacy utxo witness doesn't match the input or the transaction
+/// * InvalidAccountWitness: the account witness is not a signature of the transaction
+///   by the account key
+/// * SpendingCounterMismatch: the account witness was made with another spending counter
+///   than the account's current one
+/// * InsufficientFunds: an account doesn't have enough funds for its input
+/// * FeeMismatch: the inputs don't match the outputs plus the fee
+/// * UnknownUtxo: an input spends an output that doesn't exist or is already spent
+/// * UnknownAccount: an input spends from an account that doesn't exist
+/// * InvalidUtxoValue: the value of an input doesn't match the output it spends
+/// * MalformedTransaction: too many inputs or outputs, witnesses missing, zero outputs...
+/// * InvalidCertificate: the certificate can't be applied (unknown pool, bad signature...)
+/// * Other: any other ledger error, see the verdict's message
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FragmentVerdictKind {
+    Valid,
+    InvalidWitness,
+    InvalidAccountWitness,
+    SpendingCounterMismatch,
+    InsufficientFunds,
+    FeeMismatch,
+    UnknownUtxo,
+    UnknownAccount,
+    InvalidUtxoValue,
+    MalformedTransaction,
+    InvalidCertificate,
+    Other,
+}
+
+/// Result of checking a fragment against the ledger
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug)]
+pub struct FragmentVerdict {
+    kind: FragmentVerdictKind,
+    message: Option<String>,
+}
+
+impl FragmentVerdict {
+    fn valid() -> Self {
+        FragmentVerdict {
+            kind: FragmentVerdictKind::Valid,
+            message: None,
+        }
+    }
+
+    fn rejected(kind: FragmentVerdictKind, message: String) -> Self {
+        FragmentVerdict {
+            kind,
+            message: Some(message),
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl FragmentVerdict {
+    pub fn kind(&self) -> FragmentVerdictKind {
+        self.kind
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.kind == FragmentVerdictKind::Valid
+    }
+
+    /// Description of the ledger error, none if the fragment is valid
+    pub fn message(&self) -> Option<String> {
+        self.message.clone()
+    }
+}
+
+/// Ledger rebuilt locally to dry-run fragments before submitting them to a node
+///
+/// ```javascript
+/// const simulator = LedgerSimulator.new(block0);
+/// blocks.forEach(block => simulator.apply_block(block));
+/// const verdict = simulator.check_fragment(Fragment.from_transaction(transaction));
+/// if (!verdict.is_valid()) {
+///     console.log(verdict.kind(), verdict.message());
+/// }
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct LedgerSimulator {
+    block0_hash: key::Hash,
+    ledger: Ledger,
+    parameters: LedgerParameters,
+    tip: key::Hash,
+    tip_context: HeaderContentEvalContext,
+}
+
+impl LedgerSimulator {
+    fn verdict(&self, fragment: &Fragment, error: ledger::Error) -> FragmentVerdict {
+        use FragmentVerdictKind as K;
+        let message = format!("{}", error);
+        let kind = match error {
+            ledger::Error::UtxoInvalidSignature { .. }
+            | ledger::Error::OldUtxoInvalidSignature { .. }
+            | ledger::Error::OldUtxoInvalidPublicKey { .. }
+            | ledger::Error::MultisigInvalidSignature { .. }
+            | ledger::Error::ExpectingAccountWitness
+            | ledger::Error::ExpectingUtxoWitness => K::InvalidWitness,
+            ledger::Error::AccountInvalidSignature { account, witness } => {
+                match self.signed_spending_counter(fragment, &account, &witness) {
+                    Some(signed) => {
+                        return FragmentVerdict::rejected(
+                            K::SpendingCounterMismatch,
+                            format!(
+                                "Account witness was made with spending counter {}, expected {}",
+                                signed,
+                                self.current_spending_counter(&account).unwrap_or(0)
+                            ),
+                        )
+                    }
+                    None => K::InvalidAccountWitness,
+                }
+            }
+            ledger::Error::Account(account::LedgerError::NonExistent) => K::UnknownAccount,
+            ledger::Error::Account(account::LedgerError::ValueError(
+                value::ValueError::NegativeAmount,
+            )) => K::InsufficientFunds,
+            ledger::Error::NotBalanced { .. } => K::FeeMismatch,
+            ledger::Error::UtxoError(_) => K::UnknownUtxo,
+            ledger::Error::UtxoValueNotMatching { .. } => K::InvalidUtxoValue,
+            ledger::Error::TransactionMalformed(_)
+            | ledger::Error::NotEnoughSignatures { .. }
+            | ledger::Error::ZeroOutput { .. }
+            | ledger::Error::OutputGroupInvalid { .. }
+            | ledger::Error::InvalidDiscrimination => K::MalformedTransaction,
+            ledger::Error::Delegation(_)
+            | ledger::Error::CertificateInvalidSignature
+            | ledger::Error::PoolRegistrationInvalid => K::InvalidCertificate,
+            _ => K::Other,
+        };
+        FragmentVerdict::rejected(kind, message)
+    }
+
+    fn current_spending_counter(&self, account: &account::Identifier) -> Option<u32> {
+        self.ledger
+            .accounts()
+            .get_state(account)
+            .ok()
+            .map(|state| u32::from(state.counter))
+    }
+
+    /// the spending counter is only part of the signed data, so a witness made with
+    /// a stale (or future) counter looks like a bad signature. Look for the counter it
+    /// verifies with around the expected one.
+    fn signed_spending_counter(
+        &self,
+        fragment: &Fragment,
+        account: &account::Identifier,
+        witness: &tx::Witness,
+    ) -> Option<u32> {
+        let signature = match witness {
+            tx::Witness::Account(signature) => signature,
+            _ => return None,
+        };
+        let transaction_id = fragment.get_transaction().ok()?.id();
+        let expected = self.current_spending_counter(account)?;
+        let public_key: crypto::PublicKey<crypto::Ed25519> = account.clone().into();
+
+        let first = expected.saturating_sub(SPENDING_COUNTER_SEARCH_WINDOW);
+        let last = expected.saturating_add(SPENDING_COUNTER_SEARCH_WINDOW);
+        (first..=last)
+            .filter(|counter| *counter != expected)
+            .find(|counter| {
+                let data = tx::WitnessAccountData::new(
+                    &self.block0_hash,
+                    &transaction_id.0,
+                    &account::SpendingCounter::from(*counter),
+                );
+                signature.verify(&public_key, &data) == crypto::Verification::Success
+            })
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl LedgerSimulator {
+    /// Create the initial ledger from the genesis block
+    pub fn new(block0: &Block) -> Result<LedgerSimulator, JsChainError> {
+        let block0_hash = block0.0.id();
+        let ledger = Ledger::new(block0_hash, block0.0.fragments()).map_err(|e| {
+            JsChainError::with_source(ErrorKind::Ledger, "Failed to create the ledger", e)
+        })?;
+        let parameters = ledger.get_ledger_parameters();
+        Ok(LedgerSimulator {
+            block0_hash,
+            ledger,
+            parameters,
+            tip: block0_hash,
+            tip_context: block0.0.header.to_content_eval_context(),
+        })
+    }
+
+    /// Apply the block to the ledger, the block must be a child of the current tip
+    pub fn apply_block(&mut self, block: &Block) -> Result<(), JsChainError> {
+        if block.0.parent_id() != self.tip {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidBlock,
+                "Block is not a child of the current tip of the ledger",
+            ));
+        }
+        let context = block.0.header.to_content_eval_context();
+        self.ledger = self
+            .ledger
+            .apply_block(&self.parameters, &block.0.contents, &context)
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Ledger, "Failed to apply the block", e)
+            })?;
+        self.parameters = self.ledger.get_ledger_parameters();
+        self.tip = block.0.id();
+        self.tip_context = context;
+        Ok(())
+    }
+
+    /// Check if the fragment would be accepted on top of the current tip, without
+    /// modifying the ledger
+    pub fn check_fragment(&self, fragment: &Fragment) -> FragmentVerdict {
+        match self
+            .ledger
+            .apply_fragment(&self.parameters, &fragment.0, &self.tip_context)
+        {
+            Ok(_) => FragmentVerdict::valid(),
+            Err(error) => self.verdict(fragment, error),
+        }
+    }
+
+    /// Apply the fragment on top of the current tip, as if it was in the mempool
+    /// of the node, so the following fragments can depend on it
+    pub fn apply_fragment(&mut self, fragment: &Fragment) -> FragmentVerdict {
+        match self
+            .ledger
+            .apply_fragment(&self.parameters, &fragment.0, &self.tip_context)
+        {
+            Ok(ledger) => {
+                self.ledger = ledger;
+                FragmentVerdict::valid()
+            }
+            Err(error) => self.verdict(fragment, error),
+        }
+    }
+
+    pub fn tip(&self) -> BlockId {
+        self.tip.into()
+    }
+
+    /// Current value of the account, none if it doesn't exist
+    pub fn account_value(&self, account: &Account) -> Option<Value> {
+        match &account.0 {
+            tx::AccountIdentifier::Single(id) => self
+                .ledger
+                .accounts()
+                .get_state(id)
+                .ok()
+                .map(|state| state.value.into()),
+            tx::AccountIdentifier::Multi(_) => None,
+        }
+    }
+
+    /// Spending counter to use for the next witness of the account
+    pub fn spending_counter(&self, account: &Account) -> Option<SpendingCounter> {
+        match &account.0 {
+            tx::AccountIdentifier::Single(id) => self
+                .current_spending_counter(id)
+                .map(|counter| account::SpendingCounter::from(counter).into()),
+            tx::AccountIdentifier::Multi(_) => None,
+        }
+    }
+}
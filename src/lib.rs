@@ -2,6 +2,25 @@
 mod transaction;
 #[macro_use]
 mod utils;
+mod block0_builder;
+mod block_view;
+mod contents_proof;
+mod derivation;
+mod discovery;
+mod error;
+mod header;
+mod header_chain;
+mod history;
+mod json;
+mod leader_schedule;
+mod ledger;
+mod mnemonic;
+mod multisig;
+mod network_settings;
+mod signer;
+mod time;
+mod utxo_store;
+mod verification;
 
 use bech32::{Bech32, ToBase32 as _};
 use chain::{account, certificate, fee, key, transaction as tx, value};
@@ -13,20 +32,40 @@ use chain_crypto as crypto;
 use chain_impl_mockchain as chain;
 use crypto::bech32::Bech32 as _;
 use hex;
-use js_sys::Uint8Array;
 use rand_os::OsRng;
 use std::convert::TryFrom;
 use std::ops::{Add, Sub};
 use std::str::FromStr;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use chain_core::mempack::{ReadBuf, Readable};
 
+pub use block0_builder::Block0Builder;
+pub use block_view::{BlockStreamDecoder, BlockView, FragmentView, FragmentViews};
+pub use contents_proof::BlockContentsProof;
+pub use derivation::{DerivationPath, DerivationPurpose, DerivationRole};
+pub use discovery::AccountDiscovery;
+pub use error::{ErrorKind, JsChainError};
+pub use header::{Header, HeaderVersion};
+pub use header_chain::{HeaderChain, HeaderStatus};
+pub use history::{
+    HistoryBuilder, TransactionDirection, TransactionRecord, TransactionRecords,
+};
+pub use leader_schedule::{LeaderScheduleCalculator, LeaderSlot, LeaderSlots};
+pub use ledger::{FragmentVerdict, FragmentVerdictKind, LedgerSimulator};
+pub use mnemonic::{Mnemonic, MnemonicLanguage};
+pub use multisig::{MultisigDeclaration, MultisigPartialSignature, MultisigPartialSignatures};
+pub use network_settings::{ConsensusVersion, NetworkSettings};
+pub use signer::{InputSigner, InputSigners, PayloadSigner, PayloadSigners, SignatureKind, Signer};
+pub use time::{BlockDate, TimeConverter};
 pub use transaction::*;
+pub use utxo_store::UtxoStore;
+pub use verification::{ResolvedInput, ResolvedInputs, WitnessStatus, WitnessVerification};
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Bip32PrivateKey(crypto::SecretKey<crypto::Ed25519Bip32>);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Bip32PrivateKey {
     /// derive this private key with the given index.
     ///
@@ -51,11 +90,28 @@ impl Bip32PrivateKey {
         Bip32PrivateKey(crypto::derive::derive_sk_ed25519(&self.0, index))
     }
 
-    pub fn generate_ed25519_bip32() -> Result<Bip32PrivateKey, JsValue> {
+    /// derive this private key through every index of the given path
+    /// ```javascript
+    /// const path = DerivationPath.from_string("m/1852'/1815'/0'/0/0");
+    /// const address_key = root_key.derive_path(path);
+    /// ```
+    pub fn derive_path(&self, path: &DerivationPath) -> Bip32PrivateKey {
+        Bip32PrivateKey(path.indices().iter().fold(self.0.clone(), |key, index| {
+            crypto::derive::derive_sk_ed25519(&key, *index)
+        }))
+    }
+
+    pub fn generate_ed25519_bip32() -> Result<Bip32PrivateKey, JsChainError> {
         OsRng::new()
             .map(crypto::SecretKey::<crypto::Ed25519Bip32>::generate)
             .map(Bip32PrivateKey)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::RandomGenerator,
+                    "Failed to generate the key",
+                    e,
+                )
+            })
     }
 
     pub fn to_raw_key(&self) -> PrivateKey {
@@ -68,9 +124,11 @@ impl Bip32PrivateKey {
         Bip32PublicKey(self.0.to_public().into())
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<Bip32PrivateKey, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Bip32PrivateKey, JsChainError> {
         crypto::SecretKey::<crypto::Ed25519Bip32>::from_binary(bytes)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::InvalidKey, "Invalid bip32 private key", e)
+            })
             .map(Bip32PrivateKey)
     }
 
@@ -78,10 +136,10 @@ impl Bip32PrivateKey {
         self.0.as_ref().to_vec()
     }
 
-    pub fn from_bech32(bech32_str: &str) -> Result<Bip32PrivateKey, JsValue> {
+    pub fn from_bech32(bech32_str: &str) -> Result<Bip32PrivateKey, JsChainError> {
         crypto::SecretKey::try_from_bech32_str(&bech32_str)
             .map(Bip32PrivateKey)
-            .map_err(|_| JsValue::from_str("Invalid secret key"))
+            .map_err(|_| JsChainError::new(ErrorKind::Bech32, "Invalid secret key"))
     }
 
     pub fn to_bech32(&self) -> String {
@@ -91,12 +149,26 @@ impl Bip32PrivateKey {
     pub fn from_bip39_entropy(entropy: &[u8], password: &[u8]) -> Bip32PrivateKey {
         Bip32PrivateKey(crypto::derive::from_bip39_entropy(&entropy, &password))
     }
+
+    /// Restore the root key from a BIP39 mnemonic phrase in any of the supported languages,
+    /// this is equivalent to `from_bip39_entropy` with the entropy of the phrase
+    /// ```javascript
+    /// const root_key = Bip32PrivateKey.from_mnemonic(
+    ///     &#39;abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about&#39;,
+    ///     new Uint8Array()
+    /// );
+    /// ```
+    pub fn from_mnemonic(phrase: &str, password: &[u8]) -> Result<Bip32PrivateKey, JsChainError> {
+        mnemonic::Mnemonic::parse_any_language(phrase)
+            .map(|mnemonic| Bip32PrivateKey::from_bip39_entropy(mnemonic.entropy(), password))
+            .map_err(JsChainError::from)
+    }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Bip32PublicKey(crypto::PublicKey<crypto::Ed25519Bip32>);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Bip32PublicKey {
     /// derive this public key with the given index.
     ///
@@ -122,19 +194,47 @@ impl Bip32PublicKey {
     /// This is why deriving the private key should not fail while deriving
     /// the public key may fail (if the derivation index is invalid).
     ///
-    pub fn derive(&self, index: u32) -> Result<Bip32PublicKey, JsValue> {
+    pub fn derive(&self, index: u32) -> Result<Bip32PublicKey, JsChainError> {
         crypto::derive::derive_pk_ed25519(&self.0, index)
             .map(Bip32PublicKey)
-            .map_err(|e| JsValue::from_str(&format! {"{:?}", e}))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::Derivation,
+                    "Failed to derive the public key",
+                    format!("{:?}", e),
+                )
+            })
+    }
+
+    /// derive this public key through every index of the given path
+    ///
+    /// # Errors
+    ///
+    /// Fails if the path contains a hardened index, as those can only be
+    /// derived from the private key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Bip32PublicKey, JsChainError> {
+        path.indices()
+            .iter()
+            .try_fold(self.0.clone(), |key, index| {
+                if *index >= derivation::HARDENED {
+                    return Err(derivation::Error::HardenedDerivationFromPublicKey(*index));
+                }
+                crypto::derive::derive_pk_ed25519(&key, *index)
+                    .map_err(|_| derivation::Error::HardenedDerivationFromPublicKey(*index))
+            })
+            .map(Bip32PublicKey)
+            .map_err(JsChainError::from)
     }
 
     pub fn to_raw_key(&self) -> PublicKey {
         PublicKey(crypto::derive::to_raw_pk(&self.0))
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<Bip32PublicKey, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Bip32PublicKey, JsChainError> {
         crypto::PublicKey::<crypto::Ed25519Bip32>::from_binary(bytes)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::InvalidKey, "Invalid bip32 public key", e)
+            })
             .map(Bip32PublicKey)
     }
 
@@ -142,10 +242,12 @@ impl Bip32PublicKey {
         self.0.as_ref().to_vec()
     }
 
-    pub fn from_bech32(bech32_str: &str) -> Result<Bip32PublicKey, JsValue> {
+    pub fn from_bech32(bech32_str: &str) -> Result<Bip32PublicKey, JsChainError> {
         crypto::PublicKey::try_from_bech32_str(&bech32_str)
             .map(Bip32PublicKey)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Bech32, "Invalid bip32 public key", e)
+            })
     }
 
     pub fn to_bech32(&self) -> String {
@@ -155,10 +257,10 @@ impl Bip32PublicKey {
 
 macro_rules! impl_signature {
     ($name:ident, $signee_type:ty, $verifier_type:ty) => {
-        #[wasm_bindgen]
+        #[cfg_attr(feature = "wasm", wasm_bindgen)]
         pub struct $name(crypto::Signature<$signee_type, $verifier_type>);
 
-        #[wasm_bindgen]
+        #[cfg_attr(feature = "wasm", wasm_bindgen)]
         impl $name {
             pub fn as_bytes(&self) -> Vec<u8> {
                 self.0.as_ref().to_vec()
@@ -172,35 +274,51 @@ macro_rules! impl_signature {
                 hex::encode(&self.0.as_ref())
             }
 
-            pub fn from_bytes(bytes: &[u8]) -> Result<$name, JsValue> {
+            pub fn from_bytes(bytes: &[u8]) -> Result<$name, JsChainError> {
                 crypto::Signature::from_binary(bytes)
                     .map($name)
-                    .map_err(|e| JsValue::from_str(&format!("{}", e)))
+                    .map_err(|e| {
+                        JsChainError::with_source(
+                            ErrorKind::Deserialization,
+                            "Invalid signature",
+                            e,
+                        )
+                    })
             }
 
-            pub fn from_bech32(bech32_str: &str) -> Result<$name, JsValue> {
+            pub fn from_bech32(bech32_str: &str) -> Result<$name, JsChainError> {
                 crypto::Signature::try_from_bech32_str(&bech32_str)
                     .map($name)
-                    .map_err(|e| JsValue::from_str(&format!("{}", e)))
+                    .map_err(|e| {
+                        JsChainError::with_source(ErrorKind::Bech32, "Invalid signature", e)
+                    })
             }
 
-            pub fn from_hex(input: &str) -> Result<$name, JsValue> {
+            pub fn from_hex(input: &str) -> Result<$name, JsChainError> {
                 crypto::Signature::from_str(input)
-                    .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+                    .map_err(|e| {
+                        JsChainError::with_source(
+                            ErrorKind::Hex,
+                            "Invalid signature",
+                            format!("{:?}", e),
+                        )
+                    })
                     .map($name)
             }
         }
     };
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct LegacyDaedalusPrivateKey(crypto::SecretKey<crypto::LegacyDaedalus>);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl LegacyDaedalusPrivateKey {
-    pub fn from_bytes(bytes: &[u8]) -> Result<LegacyDaedalusPrivateKey, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<LegacyDaedalusPrivateKey, JsChainError> {
         crypto::SecretKey::<crypto::LegacyDaedalus>::from_binary(bytes)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::InvalidKey, "Invalid legacy private key", e)
+            })
             .map(LegacyDaedalusPrivateKey)
     }
 
@@ -215,7 +333,7 @@ impl_signature!(UtxoWitness, tx::WitnessUtxoData, crypto::Ed25519);
 impl_signature!(LegacyUtxoWitness, tx::WitnessUtxoData, crypto::Ed25519Bip32);
 
 /// ED25519 signing key, either normal or extended
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PrivateKey(key::EitherEd25519SecretKey);
 
 impl From<key::EitherEd25519SecretKey> for PrivateKey {
@@ -224,7 +342,7 @@ impl From<key::EitherEd25519SecretKey> for PrivateKey {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PrivateKey {
     /// Get private key from its bech32 representation
     /// ```javascript
@@ -234,7 +352,7 @@ impl PrivateKey {
     /// ```javascript
     /// PrivateKey.from_bech32(&#39;ed25519e_sk1gqwl4szuwwh6d0yk3nsqcc6xxc3fpvjlevgwvt60df59v8zd8f8prazt8ln3lmz096ux3xvhhvm3ca9wj2yctdh3pnw0szrma07rt5gl748fp&#39;);
     /// ```
-    pub fn from_bech32(bech32_str: &str) -> Result<PrivateKey, JsValue> {
+    pub fn from_bech32(bech32_str: &str) -> Result<PrivateKey, JsChainError> {
         crypto::SecretKey::try_from_bech32_str(&bech32_str)
             .map(key::EitherEd25519SecretKey::Extended)
             .or_else(|_| {
@@ -242,27 +360,39 @@ impl PrivateKey {
                     .map(key::EitherEd25519SecretKey::Normal)
             })
             .map(PrivateKey)
-            .map_err(|_| JsValue::from_str("Invalid secret key"))
+            .map_err(|_| JsChainError::new(ErrorKind::Bech32, "Invalid secret key"))
     }
 
     pub fn to_public(&self) -> PublicKey {
         self.0.to_public().into()
     }
 
-    pub fn generate_ed25519() -> Result<PrivateKey, JsValue> {
+    pub fn generate_ed25519() -> Result<PrivateKey, JsChainError> {
         OsRng::new()
             .map(crypto::SecretKey::<crypto::Ed25519>::generate)
             .map(key::EitherEd25519SecretKey::Normal)
             .map(PrivateKey)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::RandomGenerator,
+                    "Failed to generate the key",
+                    e,
+                )
+            })
     }
 
-    pub fn generate_ed25519extended() -> Result<PrivateKey, JsValue> {
+    pub fn generate_ed25519extended() -> Result<PrivateKey, JsChainError> {
         OsRng::new()
             .map(crypto::SecretKey::<crypto::Ed25519Extended>::generate)
             .map(key::EitherEd25519SecretKey::Extended)
             .map(PrivateKey)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::RandomGenerator,
+                    "Failed to generate the key",
+                    e,
+                )
+            })
     }
 
     pub fn to_bech32(&self) -> String {
@@ -279,18 +409,18 @@ impl PrivateKey {
         }
     }
 
-    pub fn from_extended_bytes(bytes: &[u8]) -> Result<PrivateKey, JsValue> {
+    pub fn from_extended_bytes(bytes: &[u8]) -> Result<PrivateKey, JsChainError> {
         crypto::SecretKey::from_binary(bytes)
             .map(key::EitherEd25519SecretKey::Extended)
             .map(PrivateKey)
-            .map_err(|_| JsValue::from_str("Invalid extended secret key"))
+            .map_err(|_| JsChainError::new(ErrorKind::InvalidKey, "Invalid extended secret key"))
     }
 
-    pub fn from_normal_bytes(bytes: &[u8]) -> Result<PrivateKey, JsValue> {
+    pub fn from_normal_bytes(bytes: &[u8]) -> Result<PrivateKey, JsChainError> {
         crypto::SecretKey::from_binary(bytes)
             .map(key::EitherEd25519SecretKey::Normal)
             .map(PrivateKey)
-            .map_err(|_| JsValue::from_str("Invalid normal secret key"))
+            .map_err(|_| JsChainError::new(ErrorKind::InvalidKey, "Invalid normal secret key"))
     }
 
     pub fn sign(&self, message: &[u8]) -> Ed25519Signature {
@@ -299,7 +429,7 @@ impl PrivateKey {
 }
 
 /// ED25519 key used as public key
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct PublicKey(crypto::PublicKey<crypto::Ed25519>);
 
@@ -309,17 +439,17 @@ impl From<crypto::PublicKey<crypto::Ed25519>> for PublicKey {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PublicKey {
     /// Get private key from its bech32 representation
     /// Example:
     /// ```javascript
     /// const pkey = PublicKey.from_bech32(&#39;ed25519_pk1dgaagyh470y66p899txcl3r0jaeaxu6yd7z2dxyk55qcycdml8gszkxze2&#39;);
     /// ```
-    pub fn from_bech32(bech32_str: &str) -> Result<PublicKey, JsValue> {
+    pub fn from_bech32(bech32_str: &str) -> Result<PublicKey, JsChainError> {
         crypto::PublicKey::try_from_bech32_str(&bech32_str)
             .map(PublicKey)
-            .map_err(|_| JsValue::from_str("Malformed public key"))
+            .map_err(|_| JsChainError::new(ErrorKind::Bech32, "Malformed public key"))
     }
 
     pub fn to_bech32(&self) -> String {
@@ -330,9 +460,9 @@ impl PublicKey {
         self.0.as_ref().to_vec()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, JsChainError> {
         crypto::PublicKey::from_binary(bytes)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| JsChainError::with_source(ErrorKind::InvalidKey, "Invalid public key", e))
             .map(PublicKey)
     }
 
@@ -341,12 +471,12 @@ impl PublicKey {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PublicKeys(Vec<PublicKey>);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PublicKeys {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> PublicKeys {
         PublicKeys(vec![])
     }
@@ -368,14 +498,14 @@ impl PublicKeys {
 //----------Address------------//
 //-----------------------------//
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SingleAddress(
     chain_addr::Discrimination,
     crypto::PublicKey<crypto::Ed25519>,
 );
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl SingleAddress {
     pub fn get_spending_key(&self) -> PublicKey {
         PublicKey::from(self.1.clone())
@@ -386,7 +516,7 @@ impl SingleAddress {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GroupAddress(
     chain_addr::Discrimination,
@@ -394,7 +524,7 @@ pub struct GroupAddress(
     crypto::PublicKey<crypto::Ed25519>,
 );
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GroupAddress {
     pub fn get_spending_key(&self) -> PublicKey {
         PublicKey::from(self.1.clone())
@@ -411,14 +541,14 @@ impl GroupAddress {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AccountAddress(
     chain_addr::Discrimination,
     crypto::PublicKey<crypto::Ed25519>,
 );
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl AccountAddress {
     pub fn get_account_key(&self) -> PublicKey {
         PublicKey::from(self.1.clone())
@@ -428,16 +558,16 @@ impl AccountAddress {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MultisigAddress(chain_addr::Discrimination, [u8; 32]);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl MultisigAddress {
     pub fn get_merkle_root(&self) -> Vec<u8> {
         self.1.to_vec()
     }
-    pub fn to_base_address(&self) -> Result<Address, JsValue> {
+    pub fn to_base_address(&self) -> Result<Address, JsChainError> {
         Address::multisig_from_merkle_root(self.get_merkle_root().as_slice(), self.0.into())
     }
 }
@@ -446,17 +576,17 @@ impl MultisigAddress {
 /// * A utxo-based address without delegation (single)
 /// * A utxo-based address with delegation (group)
 /// * An address for an account
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Address(chain_addr::Address);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Address {
-    pub fn from_bytes(bytes: Uint8Array) -> Result<Address, JsValue> {
-        let mut slice: Box<[u8]> = vec![0; bytes.length() as usize].into_boxed_slice();
-        bytes.copy_to(&mut *slice);
-        chain_addr::Address::deserialize(&*slice)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+    pub fn from_bytes(bytes: &[u8]) -> Result<Address, JsChainError> {
+        chain_addr::Address::deserialize(bytes)
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid address", e)
+            })
             .map(Address)
     }
 
@@ -470,10 +600,16 @@ impl Address {
     /// ```javascript
     /// const address = Address.from_string(&#39;ca1q09u0nxmnfg7af8ycuygx57p5xgzmnmgtaeer9xun7hly6mlgt3pjyknplu&#39;);
     /// ```
-    pub fn from_string(s: &str) -> Result<Address, JsValue> {
+    pub fn from_string(s: &str) -> Result<Address, JsChainError> {
         chain_addr::AddressReadable::from_string_anyprefix(s)
             .map(|address_readable| Address(address_readable.to_address()))
-            .map_err(|e| JsValue::from_str(&format! {"{:?}", e}))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::InvalidAddress,
+                    "Invalid address",
+                    format!("{:?}", e),
+                )
+            })
     }
 
     /// Get Address bech32 (string) representation with a given prefix
@@ -539,7 +675,7 @@ impl Address {
     pub fn multisig_from_merkle_root(
         merkle_root: &[u8],
         discrimination: AddressDiscrimination,
-    ) -> Result<Address, JsValue> {
+    ) -> Result<Address, JsChainError> {
         match merkle_root.len() {
             32 => {
                 let mut sized_root = [0; 32];
@@ -550,7 +686,10 @@ impl Address {
                 )
                 .into())
             }
-            _ => Err(JsValue::from_str("Invalid merkle root size")),
+            _ => Err(JsChainError::new(
+                ErrorKind::InvalidLength,
+                "Invalid merkle root size",
+            )),
         }
     }
 
@@ -616,7 +755,7 @@ impl From<chain_addr::Address> for Address {
 /// let discriminant = AddressDiscrimination.Test;
 /// let address = Address::single_from_public_key(public_key, discriminant);
 /// ```
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum AddressDiscrimination {
     Production,
     Test,
@@ -639,7 +778,7 @@ impl From<chain_addr::Discrimination> for AddressDiscrimination {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum AddressKind {
     Single,
     Group,
@@ -668,14 +807,16 @@ impl From<chain_addr::KindType> for AddressKind {
     }
 }
 
+impl_collection!(Addresses, Address);
 impl_collection!(Outputs, Output);
 impl_collection!(Inputs, Input);
 impl_collection!(Fragments, Fragment);
+impl_collection!(UtxoPointers, UtxoPointer);
 
 /// Helper to add change addresses when finalizing a transaction, there are currently two options
 /// * forget: use all the excess money as fee
 /// * one: send all the excess money to the given address
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct OutputPolicy(tx::OutputPolicy);
 
 impl From<tx::OutputPolicy> for OutputPolicy {
@@ -684,7 +825,7 @@ impl From<tx::OutputPolicy> for OutputPolicy {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl OutputPolicy {
     /// don't do anything with the excess money in transaction
     pub fn forget() -> OutputPolicy {
@@ -698,20 +839,32 @@ impl OutputPolicy {
 }
 
 /// Type for representing the hash of a Transaction, necessary for signing it
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TransactionSignDataHash(tx::TransactionSignDataHash);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TransactionSignDataHash {
-    pub fn from_bytes(bytes: &[u8]) -> Result<TransactionSignDataHash, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<TransactionSignDataHash, JsChainError> {
         tx::TransactionSignDataHash::try_from(bytes)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::InvalidLength,
+                    "Invalid transaction sign data hash",
+                    e,
+                )
+            })
             .map(|digest| digest.into())
     }
 
-    pub fn from_hex(input: &str) -> Result<TransactionSignDataHash, JsValue> {
+    pub fn from_hex(input: &str) -> Result<TransactionSignDataHash, JsChainError> {
         tx::TransactionSignDataHash::from_str(input)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::Hex,
+                    "Invalid transaction sign data hash",
+                    format!("{:?}", e),
+                )
+            })
             .map(TransactionSignDataHash)
     }
 
@@ -727,7 +880,7 @@ impl From<tx::TransactionSignDataHash> for TransactionSignDataHash {
 }
 
 /// Type for representing a generic Hash
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Hash(key::Hash);
 
 impl From<key::Hash> for Hash {
@@ -736,15 +889,15 @@ impl From<key::Hash> for Hash {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Hash {
     pub fn calculate(bytes: &[u8]) -> Hash {
         key::Hash::hash_bytes(bytes).into()
     }
 
-    pub fn from_hex(hex_string: &str) -> Result<Hash, JsValue> {
+    pub fn from_hex(hex_string: &str) -> Result<Hash, JsChainError> {
         key::Hash::from_str(hex_string)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| JsChainError::with_source(ErrorKind::Hex, "Invalid hash", e))
             .map(Hash)
     }
 
@@ -753,7 +906,7 @@ impl Hash {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct Input(tx::Input);
 
@@ -763,7 +916,7 @@ impl From<tx::Input> for Input {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum InputKind {
     Account,
     Utxo,
@@ -773,7 +926,7 @@ pub enum InputKind {
 /// either contains an account reference or a TransactionSignDataHash+index
 ///
 /// This uniquely refer to a specific source of value.
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Input {
     pub fn from_utxo(utxo_pointer: &UtxoPointer) -> Self {
         Input(tx::Input::from_utxo(utxo_pointer.0))
@@ -810,18 +963,24 @@ impl Input {
     }
 
     /// Get the inner UtxoPointer if the Input type is Utxo
-    pub fn get_utxo_pointer(&self) -> Result<UtxoPointer, JsValue> {
+    pub fn get_utxo_pointer(&self) -> Result<UtxoPointer, JsChainError> {
         match self.0.to_enum() {
             tx::InputEnum::UtxoInput(utxo_pointer) => Ok(utxo_pointer.into()),
-            _ => Err(JsValue::from_str("Input is not from utxo")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Input is not from utxo",
+            )),
         }
     }
 
     /// Get the source Account if the Input type is Account
-    pub fn get_account_identifier(&self) -> Result<AccountIdentifier, JsValue> {
+    pub fn get_account_identifier(&self) -> Result<AccountIdentifier, JsChainError> {
         match self.0.to_enum() {
             tx::InputEnum::AccountInput(account, _) => Ok(AccountIdentifier(account)),
-            _ => Err(JsValue::from_str("Input is not from account")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Input is not from account",
+            )),
         }
     }
 
@@ -829,11 +988,9 @@ impl Input {
         self.0.bytes().to_vec()
     }
 
-    pub fn from_bytes(bytes: Uint8Array) -> Result<Input, JsValue> {
-        let mut slice: Box<[u8]> = vec![0; bytes.length() as usize].into_boxed_slice();
-        bytes.copy_to(&mut *slice);
-        tx::Input::deserialize(&*slice)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+    pub fn from_bytes(bytes: &[u8]) -> Result<Input, JsChainError> {
+        tx::Input::deserialize(bytes)
+            .map_err(|e| JsChainError::with_source(ErrorKind::Deserialization, "Invalid input", e))
             .map(Input)
     }
 }
@@ -843,8 +1000,8 @@ impl Input {
 /// * the output index within the pointed transaction's outputs
 /// * the value we expect to read from this output, this setting is added in order to protect undesired withdrawal
 /// and to set the actual fee in the transaction.
-#[wasm_bindgen]
-#[derive(Debug)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug)]
 pub struct UtxoPointer(tx::UtxoPointer);
 
 impl From<tx::UtxoPointer> for UtxoPointer {
@@ -853,7 +1010,7 @@ impl From<tx::UtxoPointer> for UtxoPointer {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl UtxoPointer {
     pub fn new(fragment_id: &FragmentId, output_index: u8, value: &Value) -> UtxoPointer {
         UtxoPointer(tx::UtxoPointer {
@@ -873,7 +1030,7 @@ impl UtxoPointer {
 }
 
 /// This is either an single account or a multisig account depending on the witness type
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Debug)]
 pub struct Account(tx::AccountIdentifier);
 
@@ -883,9 +1040,9 @@ impl From<tx::AccountIdentifier> for Account {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Account {
-    pub fn from_address(address: &Address) -> Result<Account, JsValue> {
+    pub fn from_address(address: &Address) -> Result<Account, JsChainError> {
         match address.0.kind() {
             chain_addr::Kind::Account(key) => {
                 Ok(Account(tx::AccountIdentifier::Single(key.clone().into())))
@@ -893,7 +1050,10 @@ impl Account {
             chain_addr::Kind::Multisig(id) => {
                 Ok(Account(tx::AccountIdentifier::Multi(id.clone().into())))
             }
-            _ => Err(JsValue::from_str("Address is not account")),
+            _ => Err(JsChainError::new(
+                ErrorKind::NotAccountAddress,
+                "Address is not account",
+            )),
         }
     }
 
@@ -927,21 +1087,24 @@ impl Account {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct AccountIdentifier(tx::UnspecifiedAccountIdentifier);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl AccountIdentifier {
     pub fn to_hex(&self) -> String {
         hex::encode(self.0.as_ref())
     }
 
-    pub fn to_account_single(&self) -> Result<Account, JsValue> {
+    pub fn to_account_single(&self) -> Result<Account, JsChainError> {
         self.0
             .to_single_account()
-            .ok_or(JsValue::from_str(
-                "can't be used as a public key for single account",
-            ))
+            .ok_or_else(|| {
+                JsChainError::new(
+                    ErrorKind::NotAccountAddress,
+                    "can't be used as a public key for single account",
+                )
+            })
             .map(|acc| Account(tx::AccountIdentifier::Single(acc)))
     }
 
@@ -951,7 +1114,7 @@ impl AccountIdentifier {
 }
 
 /// Type for representing a Transaction Output, composed of an Address and a Value
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct Output(tx::Output<chain_addr::Address>);
 
@@ -961,8 +1124,12 @@ impl From<tx::Output<chain_addr::Address>> for Output {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Output {
+    pub fn new(address: &Address, value: &Value) -> Output {
+        Output(tx::Output::from_address(address.0.clone(), value.0))
+    }
+
     pub fn address(&self) -> Address {
         self.0.address.clone().into()
     }
@@ -977,7 +1144,7 @@ impl Output {
 /// Strings are used for passing to and from javascript,
 /// as the native javascript Number type can't hold the entire u64 range
 /// and BigInt is not yet implemented in all the browsers
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Debug, Eq, PartialEq)]
 pub struct Value(value::Value);
 
@@ -993,12 +1160,18 @@ impl From<u64> for Value {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Value {
     /// Parse the given string into a rust u64 numeric type.
-    pub fn from_str(s: &str) -> Result<Value, JsValue> {
+    pub fn from_str(s: &str) -> Result<Value, JsChainError> {
         s.parse::<u64>()
-            .map_err(|e| JsValue::from_str(&format! {"{:?}", e}))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::InvalidValue,
+                    "Invalid value",
+                    format!("{:?}", e),
+                )
+            })
             .map(|number| number.into())
     }
 
@@ -1007,17 +1180,17 @@ impl Value {
         format!("{}", self.0)
     }
 
-    pub fn checked_add(&self, other: &Value) -> Result<Value, JsValue> {
+    pub fn checked_add(&self, other: &Value) -> Result<Value, JsChainError> {
         self.0
             .add(other.0)
-            .map_err(|e| JsValue::from_str(&format!("{}", &format!("{}", e))))
+            .map_err(|e| JsChainError::with_source(ErrorKind::InvalidValue, "Value overflow", e))
             .map(Value)
     }
 
-    pub fn checked_sub(&self, other: &Value) -> Result<Value, JsValue> {
+    pub fn checked_sub(&self, other: &Value) -> Result<Value, JsChainError> {
         self.0
             .sub(other.0)
-            .map_err(|e| JsValue::from_str(&format!("{}", &format!("{}", e))))
+            .map_err(|e| JsChainError::with_source(ErrorKind::InvalidValue, "Value underflow", e))
             .map(Value)
     }
 }
@@ -1028,7 +1201,7 @@ impl From<value::Value> for Value {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct U128(u128);
 
 impl From<u128> for U128 {
@@ -1037,37 +1210,43 @@ impl From<u128> for U128 {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl U128 {
-    pub fn from_be_bytes(bytes: Uint8Array) -> Result<U128, JsValue> {
-        if bytes.length() == std::mem::size_of::<u128>() as u32 {
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<U128, JsChainError> {
+        if bytes.len() == std::mem::size_of::<u128>() {
             let mut slice = [0u8; 16];
-            bytes.copy_to(&mut slice);
+            slice.copy_from_slice(bytes);
             Ok(u128::from_be_bytes(slice).into())
         } else {
-            Err(JsValue::from_str(&format!(
-                "Invalid array length. Found {}, expected: 16",
-                bytes.length()
-            )))
+            Err(JsChainError::new(
+                ErrorKind::InvalidLength,
+                format!("Invalid array length. Found {}, expected: 16", bytes.len()),
+            ))
         }
     }
 
-    pub fn from_le_bytes(bytes: Uint8Array) -> Result<U128, JsValue> {
-        if bytes.length() == std::mem::size_of::<u128>() as u32 {
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<U128, JsChainError> {
+        if bytes.len() == std::mem::size_of::<u128>() {
             let mut slice = [0u8; 16];
-            bytes.copy_to(&mut slice);
+            slice.copy_from_slice(bytes);
             Ok(u128::from_le_bytes(slice).into())
         } else {
-            Err(JsValue::from_str(&format!(
-                "Invalid array length. Found {}, expected: 16",
-                bytes.length()
-            )))
+            Err(JsChainError::new(
+                ErrorKind::InvalidLength,
+                format!("Invalid array length. Found {}, expected: 16", bytes.len()),
+            ))
         }
     }
 
-    pub fn from_str(s: &str) -> Result<U128, JsValue> {
+    pub fn from_str(s: &str) -> Result<U128, JsChainError> {
         s.parse::<u128>()
-            .map_err(|e| JsValue::from_str(&format! {"{:?}", e}))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::InvalidValue,
+                    "Invalid u128",
+                    format!("{:?}", e),
+                )
+            })
             .map(U128)
     }
 
@@ -1076,7 +1255,7 @@ impl U128 {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct GenesisPraosLeaderHash(chain::certificate::GenesisPraosLeaderHash);
 
@@ -1086,11 +1265,13 @@ impl From<chain::certificate::GenesisPraosLeaderHash> for GenesisPraosLeaderHash
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GenesisPraosLeaderHash {
-    pub fn from_hex(hex_string: &str) -> Result<GenesisPraosLeaderHash, JsValue> {
+    pub fn from_hex(hex_string: &str) -> Result<GenesisPraosLeaderHash, JsChainError> {
         crypto::Blake2b256::from_str(hex_string)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Hex, "Invalid leader hash", format!("{:?}", e))
+            })
             .map(|hash| GenesisPraosLeaderHash(hash.into()))
     }
 
@@ -1099,7 +1280,7 @@ impl GenesisPraosLeaderHash {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct GenesisPraosLeader(chain::leadership::genesis::GenesisPraosLeader);
 
 impl From<chain::leadership::genesis::GenesisPraosLeader> for GenesisPraosLeader {
@@ -1108,7 +1289,7 @@ impl From<chain::leadership::genesis::GenesisPraosLeader> for GenesisPraosLeader
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl GenesisPraosLeader {
     pub fn new(
         kes_public_key: &KesPublicKey,
@@ -1120,9 +1301,67 @@ impl GenesisPraosLeader {
         })
     }
 
+    /// Generate new KES and VRF keys, the secret keys are kept by the stake
+    /// pool operator and the public ones go in the pool registration
+    ///
+    /// ```javascript
+    /// const secrets = GenesisPraosLeader.generate();
+    /// const leader = secrets.leader();
+    /// save(secrets.kes_secret_key().to_bech32(), secrets.vrf_secret_key().to_bech32());
+    /// ```
+    pub fn generate() -> Result<GenesisPraosLeaderSecrets, JsChainError> {
+        Ok(GenesisPraosLeaderSecrets {
+            kes_secret_key: KesSecretKey::generate()?,
+            vrf_secret_key: VrfSecretKey::generate()?,
+        })
+    }
+
+    pub fn kes_public_key(&self) -> KesPublicKey {
+        KesPublicKey(self.0.kes_public_key.clone())
+    }
+
+    pub fn vrf_public_key(&self) -> VrfPublicKey {
+        VrfPublicKey(self.0.vrf_public_key.clone())
+    }
 }
 
-#[wasm_bindgen]
+/// Secret keys of a stake pool leader
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct GenesisPraosLeaderSecrets {
+    kes_secret_key: KesSecretKey,
+    vrf_secret_key: VrfSecretKey,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl GenesisPraosLeaderSecrets {
+    pub fn new(
+        kes_secret_key: &KesSecretKey,
+        vrf_secret_key: &VrfSecretKey,
+    ) -> GenesisPraosLeaderSecrets {
+        GenesisPraosLeaderSecrets {
+            kes_secret_key: kes_secret_key.clone(),
+            vrf_secret_key: vrf_secret_key.clone(),
+        }
+    }
+
+    pub fn kes_secret_key(&self) -> KesSecretKey {
+        self.kes_secret_key.clone()
+    }
+
+    pub fn vrf_secret_key(&self) -> VrfSecretKey {
+        self.vrf_secret_key.clone()
+    }
+
+    /// The public keys, to register the stake pool
+    pub fn leader(&self) -> GenesisPraosLeader {
+        GenesisPraosLeader::new(
+            &self.kes_secret_key.to_public(),
+            &self.vrf_secret_key.to_public(),
+        )
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Certificate(certificate::Certificate);
 
 impl From<certificate::Certificate> for Certificate {
@@ -1131,7 +1370,7 @@ impl From<certificate::Certificate> for Certificate {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PoolRegistration(chain::certificate::PoolRegistration);
 
 impl From<chain::certificate::PoolRegistration> for PoolRegistration {
@@ -1140,7 +1379,7 @@ impl From<chain::certificate::PoolRegistration> for PoolRegistration {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct StakeDelegation(chain::certificate::StakeDelegation);
 
 impl From<chain::certificate::StakeDelegation> for StakeDelegation {
@@ -1149,7 +1388,7 @@ impl From<chain::certificate::StakeDelegation> for StakeDelegation {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 /// Set the choice of delegation:
 ///
 /// * No delegation
@@ -1163,14 +1402,14 @@ impl From<chain::account::DelegationType> for DelegationType {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub enum DelegationKind {
     NonDelegated,
     Full,
     Ratio,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl DelegationType {
     pub fn non_delegated() -> Self {
         Self(chain::account::DelegationType::NonDelegated)
@@ -1210,17 +1449,17 @@ impl DelegationType {
 /// It's invalid to have less than 2 elements in the array,
 /// and by extension parts need to be equal to the sum of individual
 /// pools parts.
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct DelegationRatio(chain::account::DelegationRatio);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct PoolDelegationRatio {
     pool: PoolId,
     part: u8,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolDelegationRatio {
     //TODO: Add constructor attribute
     pub fn new(pool: &PoolId, part: u8) -> PoolDelegationRatio {
@@ -1233,7 +1472,7 @@ impl PoolDelegationRatio {
 
 impl_collection!(PoolDelegationRatios, PoolDelegationRatio);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl DelegationRatio {
     //TODO: Add constructor attribute
     pub fn new(parts: u8, pools: &PoolDelegationRatios) -> Option<DelegationRatio> {
@@ -1248,7 +1487,7 @@ impl DelegationRatio {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl StakeDelegation {
     /// Create a stake delegation object from account (stake key) to pool_id
     pub fn new(delegation_type: &DelegationType, account: &PublicKey) -> StakeDelegation {
@@ -1273,15 +1512,17 @@ impl StakeDelegation {
         self.0.serialize().as_ref().to_vec()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<StakeDelegation, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<StakeDelegation, JsChainError> {
         let mut buf = ReadBuf::from(&bytes);
         chain::certificate::StakeDelegation::read(&mut buf)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid stake delegation", e)
+            })
             .map(StakeDelegation)
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct OwnerStakeDelegation(chain::certificate::OwnerStakeDelegation);
 
 impl From<chain::certificate::OwnerStakeDelegation> for OwnerStakeDelegation {
@@ -1290,7 +1531,7 @@ impl From<chain::certificate::OwnerStakeDelegation> for OwnerStakeDelegation {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl OwnerStakeDelegation {
     pub fn new(delegation_type: &DelegationType) -> OwnerStakeDelegation {
         certificate::OwnerStakeDelegation {
@@ -1307,15 +1548,21 @@ impl OwnerStakeDelegation {
         self.0.serialize().as_ref().to_vec()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<OwnerStakeDelegation, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<OwnerStakeDelegation, JsChainError> {
         let mut buf = ReadBuf::from(&bytes);
         chain::certificate::OwnerStakeDelegation::read(&mut buf)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::Deserialization,
+                    "Invalid owner stake delegation",
+                    e,
+                )
+            })
             .map(OwnerStakeDelegation)
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PoolRetirement(chain::certificate::PoolRetirement);
 
 impl From<chain::certificate::PoolRetirement> for PoolRetirement {
@@ -1324,7 +1571,7 @@ impl From<chain::certificate::PoolRetirement> for PoolRetirement {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolRetirement {
     pub fn new(pool_id: &PoolId, retirement_time_offset: &TimeOffsetSeconds) -> Self {
         chain::certificate::PoolRetirement {
@@ -1346,15 +1593,17 @@ impl PoolRetirement {
         self.0.serialize().as_ref().to_vec()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<PoolRetirement, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<PoolRetirement, JsChainError> {
         let mut buf = ReadBuf::from(&bytes);
         chain::certificate::PoolRetirement::read(&mut buf)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid pool retirement", e)
+            })
             .map(PoolRetirement)
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PoolUpdate(chain::certificate::PoolUpdate);
 
 impl From<chain::certificate::PoolUpdate> for PoolUpdate {
@@ -1363,7 +1612,7 @@ impl From<chain::certificate::PoolUpdate> for PoolUpdate {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolUpdate {
     pub fn new(
         pool_id: &PoolId,
@@ -1400,15 +1649,18 @@ impl PoolUpdate {
         self.0.serialize().as_ref().to_vec()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<PoolUpdate, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<PoolUpdate, JsChainError> {
         let mut buf = ReadBuf::from(&bytes);
         chain::certificate::PoolUpdate::read(&mut buf)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid pool update", e)
+            })
             .map(PoolUpdate)
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CertificateKind {
     StakeDelegation,
     OwnerStakeDelegation,
@@ -1416,7 +1668,7 @@ pub enum CertificateKind {
     PoolRetirement,
     PoolUpdate,
 }
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Certificate {
     /// Create a Certificate for StakeDelegation
     pub fn stake_delegation(stake_delegation: &StakeDelegation) -> Certificate {
@@ -1455,38 +1707,53 @@ impl Certificate {
         }
     }
 
-    pub fn get_stake_delegation(&self) -> Result<StakeDelegation, JsValue> {
+    pub fn get_stake_delegation(&self) -> Result<StakeDelegation, JsChainError> {
         match &self.0 {
             certificate::Certificate::StakeDelegation(cert) => Ok(cert.clone().into()),
-            _ => Err(JsValue::from_str("Certificate is not StakeDelegation")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Certificate is not StakeDelegation",
+            )),
         }
     }
 
-    pub fn get_owner_stake_delegation(&self) -> Result<OwnerStakeDelegation, JsValue> {
+    pub fn get_owner_stake_delegation(&self) -> Result<OwnerStakeDelegation, JsChainError> {
         match &self.0 {
             certificate::Certificate::OwnerStakeDelegation(cert) => Ok(cert.clone().into()),
-            _ => Err(JsValue::from_str("Certificate is not OwnerStakeDelegation")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Certificate is not OwnerStakeDelegation",
+            )),
         }
     }
 
-    pub fn get_pool_registration(&self) -> Result<PoolRegistration, JsValue> {
+    pub fn get_pool_registration(&self) -> Result<PoolRegistration, JsChainError> {
         match &self.0 {
             certificate::Certificate::PoolRegistration(cert) => Ok(cert.clone().into()),
-            _ => Err(JsValue::from_str("Certificate is not PoolRegistration")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Certificate is not PoolRegistration",
+            )),
         }
     }
 
-    pub fn get_pool_retirement(&self) -> Result<PoolRetirement, JsValue> {
+    pub fn get_pool_retirement(&self) -> Result<PoolRetirement, JsChainError> {
         match &self.0 {
             certificate::Certificate::PoolRetirement(cert) => Ok(cert.clone().into()),
-            _ => Err(JsValue::from_str("Certificate is not PoolRetirement")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Certificate is not PoolRetirement",
+            )),
         }
     }
 
-    pub fn get_pool_update(&self) -> Result<PoolUpdate, JsValue> {
+    pub fn get_pool_update(&self) -> Result<PoolUpdate, JsChainError> {
         match &self.0 {
             certificate::Certificate::PoolUpdate(cert) => Ok(cert.clone().into()),
-            _ => Err(JsValue::from_str("Certificate is not PoolUpdate")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Certificate is not PoolUpdate",
+            )),
         }
     }
 
@@ -1501,9 +1768,25 @@ impl Certificate {
     }
 }
 
-#[wasm_bindgen]
+fn check_management_threshold(threshold: u8, owners: usize) -> Result<(), JsChainError> {
+    if threshold == 0 || usize::from(threshold) > owners {
+        return Err(JsChainError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "The management threshold must be between 1 and the number of owners ({})",
+                owners
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolRegistration {
-    #[wasm_bindgen(constructor)]
+    /// The management threshold is the number of owners needed to sign the
+    /// updates and the retirement of the pool. The rewards of the pool are
+    /// paid to the reward account, or to the owners if there is none
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new(
         serial: &U128,
         owners: &PublicKeys,
@@ -1511,21 +1794,22 @@ impl PoolRegistration {
         management_threshold: u8,
         start_validity: &TimeOffsetSeconds,
         leader_keys: &GenesisPraosLeader,
-    ) -> PoolRegistration {
+        rewards: &TaxType,
+        reward_account: Option<Account>,
+    ) -> Result<PoolRegistration, JsChainError> {
         use chain::certificate::PoolPermissions;
-        chain::certificate::PoolRegistration {
+        check_management_threshold(management_threshold, owners.0.len())?;
+        Ok(chain::certificate::PoolRegistration {
             serial: serial.0.clone(),
             owners: owners.0.clone().into_iter().map(|key| key.0).collect(),
             operators: operators.0.clone().into_iter().map(|key| key.0).collect(),
             permissions: PoolPermissions::new(management_threshold),
             start_validity: start_validity.0.clone(),
-            // TODO: Hardcoded parameter
-            rewards: chain::rewards::TaxType::zero(),
-            // TODO: Hardcoded parameter
-            reward_account: None,
+            rewards: rewards.0,
+            reward_account: reward_account.map(|account| account.0),
             keys: leader_keys.0.clone(),
         }
-        .into()
+        .into())
     }
 
     pub fn id(&self) -> PoolId {
@@ -1536,7 +1820,19 @@ impl PoolRegistration {
         self.0.start_validity.into()
     }
 
-    // TODO: missing PoolPermissions. Don't think we need this for now
+    /// Number of owners needed to sign the updates and the retirement of the pool
+    pub fn management_threshold(&self) -> u8 {
+        self.0.permissions.management_threshold()
+    }
+
+    pub fn set_management_threshold(
+        &mut self,
+        management_threshold: u8,
+    ) -> Result<(), JsChainError> {
+        check_management_threshold(management_threshold, self.0.owners.len())?;
+        self.0.permissions = chain::certificate::PoolPermissions::new(management_threshold);
+        Ok(())
+    }
 
     pub fn owners(&self) -> PublicKeys {
         PublicKeys(self.0.owners.iter().map(|key| key.clone().into()).collect())
@@ -1554,6 +1850,15 @@ impl PoolRegistration {
         self.0.reward_account.as_ref().map(|acc| Account(acc.clone()))
     }
 
+    pub fn set_rewards(&mut self, rewards: &TaxType) {
+        self.0.rewards = rewards.0;
+    }
+
+    /// Pay the rewards to the given account, or to the owners if none
+    pub fn set_reward_account(&mut self, reward_account: Option<Account>) {
+        self.0.reward_account = reward_account.map(|account| account.0);
+    }
+
     pub fn keys(&self) -> GenesisPraosLeader {
         GenesisPraosLeader(self.0.keys.clone())
     }
@@ -1562,15 +1867,21 @@ impl PoolRegistration {
         self.0.serialize().as_ref().to_vec()
     }
 
-    pub fn from_bytes(bytes: &[u8]) -> Result<PoolRegistration, JsValue> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<PoolRegistration, JsChainError> {
         let mut buf = ReadBuf::from(&bytes);
         chain::certificate::PoolRegistration::read(&mut buf)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::Deserialization,
+                    "Invalid pool registration",
+                    e,
+                )
+            })
             .map(PoolRegistration)
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TaxType(chain::rewards::TaxType);
 
 impl From<chain::rewards::TaxType> for TaxType {
@@ -1579,7 +1890,58 @@ impl From<chain::rewards::TaxType> for TaxType {
     }
 }
 
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TaxType {
+    /// The pool takes `fixed` from the rewards of each epoch, then the ratio
+    /// of the rest, the ratio part being capped to `max_limit` if any
+    ///
+    /// ```javascript
+    /// // 10 ada and 5% of the rest, up to 100 ada
+    /// const tax = TaxType.new(
+    ///     Value.from_str(&#39;10000000&#39;),
+    ///     Value.from_str(&#39;5&#39;),
+    ///     Value.from_str(&#39;100&#39;),
+    ///     Value.from_str(&#39;100000000&#39;),
+    /// );
+    /// ```
+    pub fn new(
+        fixed: &Value,
+        ratio_numerator: &Value,
+        ratio_denominator: &Value,
+        max_limit: Option<Value>,
+    ) -> Result<TaxType, JsChainError> {
+        let numerator = *ratio_numerator.0.as_ref();
+        let denominator = std::num::NonZeroU64::new(*ratio_denominator.0.as_ref())
+            .ok_or_else(|| {
+                JsChainError::new(ErrorKind::InvalidValue, "The ratio denominator can't be zero")
+            })?;
+        if numerator > denominator.get() {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidValue,
+                "The ratio can't be greater than one",
+            ));
+        }
+        let max_limit = match max_limit {
+            Some(limit) => Some(std::num::NonZeroU64::new(*limit.0.as_ref()).ok_or_else(|| {
+                JsChainError::new(ErrorKind::InvalidValue, "The maximum limit can't be zero")
+            })?),
+            None => None,
+        };
+        Ok(TaxType(chain::rewards::TaxType {
+            fixed: fixed.0,
+            ratio: chain::rewards::Ratio {
+                numerator,
+                denominator,
+            },
+            max_limit,
+        }))
+    }
+
+    /// No tax, all the rewards go to the stakers
+    pub fn zero() -> TaxType {
+        TaxType(chain::rewards::TaxType::zero())
+    }
+
     pub fn fixed(&self) -> Value {
         self.0.fixed.into()
     }
@@ -1597,7 +1959,7 @@ impl TaxType {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TimeOffsetSeconds(chain_time::timeline::TimeOffsetSeconds);
 
 impl From<chain_time::timeline::TimeOffsetSeconds> for TimeOffsetSeconds {
@@ -1606,13 +1968,19 @@ impl From<chain_time::timeline::TimeOffsetSeconds> for TimeOffsetSeconds {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TimeOffsetSeconds {
     /// Parse the given string into a 64 bits unsigned number
-    pub fn from_string(number: &str) -> Result<TimeOffsetSeconds, JsValue> {
+    pub fn from_string(number: &str) -> Result<TimeOffsetSeconds, JsChainError> {
         number
             .parse::<u64>()
-            .map_err(|e| JsValue::from_str(&format! {"{:?}", e}))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::InvalidValue,
+                    "Invalid time offset",
+                    format!("{:?}", e),
+                )
+            })
             .map(chain_time::DurationSeconds)
             .map(|duration| chain_time::timeline::TimeOffsetSeconds::from(duration).into())
     }
@@ -1622,7 +1990,7 @@ impl TimeOffsetSeconds {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct PoolId(chain::certificate::PoolId);
 
@@ -1632,11 +2000,13 @@ impl From<chain::certificate::PoolId> for PoolId {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolId {
-    pub fn from_hex(hex_string: &str) -> Result<PoolId, JsValue> {
+    pub fn from_hex(hex_string: &str) -> Result<PoolId, JsChainError> {
         crypto::Blake2b256::from_str(hex_string)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Hex, "Invalid pool id", format!("{:?}", e))
+            })
             .map(|hash| PoolId(hash.into()))
     }
 
@@ -1645,7 +2015,7 @@ impl PoolId {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct KesPublicKey(crypto::PublicKey<crypto::SumEd25519_12>);
 
 impl From<crypto::PublicKey<crypto::SumEd25519_12>> for KesPublicKey {
@@ -1654,16 +2024,114 @@ impl From<crypto::PublicKey<crypto::SumEd25519_12>> for KesPublicKey {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl KesPublicKey {
-    pub fn from_bech32(bech32_str: &str) -> Result<KesPublicKey, JsValue> {
+    pub fn from_bech32(bech32_str: &str) -> Result<KesPublicKey, JsChainError> {
         crypto::PublicKey::try_from_bech32_str(&bech32_str)
             .map(KesPublicKey)
-            .map_err(|_| JsValue::from_str("Malformed kes public key"))
+            .map_err(|_| JsChainError::new(ErrorKind::Bech32, "Malformed kes public key"))
+    }
+
+    pub fn to_bech32(&self) -> String {
+        self.0.to_bech32_str()
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.0.as_ref().to_vec()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<KesPublicKey, JsChainError> {
+        crypto::PublicKey::from_binary(bytes)
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::InvalidKey, "Invalid kes public key", e)
+            })
+            .map(KesPublicKey)
     }
 }
 
-#[wasm_bindgen]
+/// Number of periods of the KES keys, a key can sign block headers for that
+/// many periods before a new one must be registered
+const KES_PERIODS: u32 = 1 << 12;
+
+/// Key evolving signature key signing the block headers of a stake pool
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct KesSecretKey(crypto::SecretKey<crypto::SumEd25519_12>);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl KesSecretKey {
+    pub fn generate() -> Result<KesSecretKey, JsChainError> {
+        OsRng::new()
+            .map(crypto::SecretKey::<crypto::SumEd25519_12>::generate)
+            .map(KesSecretKey)
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::RandomGenerator,
+                    "Failed to generate the key",
+                    e,
+                )
+            })
+    }
+
+    pub fn to_public(&self) -> KesPublicKey {
+        KesPublicKey(self.0.to_public())
+    }
+
+    pub fn from_bech32(bech32_str: &str) -> Result<KesSecretKey, JsChainError> {
+        crypto::SecretKey::try_from_bech32_str(&bech32_str)
+            .map(KesSecretKey)
+            .map_err(|_| JsChainError::new(ErrorKind::Bech32, "Invalid kes secret key"))
+    }
+
+    pub fn to_bech32(&self) -> String {
+        self.0.to_bech32_str()
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.0.as_ref().to_vec()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<KesSecretKey, JsChainError> {
+        crypto::SecretKey::from_binary(bytes)
+            .map(KesSecretKey)
+            .map_err(|_| JsChainError::new(ErrorKind::InvalidKey, "Invalid kes secret key"))
+    }
+
+    /// Period the key signs for, the key starts at period 0
+    pub fn period(&self) -> u32 {
+        // the serialized key starts with its period
+        let bytes = self.0.as_ref();
+        u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Evolve the key to the given period, the key can't go back to a
+    /// previous period once evolved
+    pub fn update(&mut self, period: u32) -> Result<(), JsChainError> {
+        use crypto::{AsymmetricKey as _, KeyEvolvingSignatureAlgorithm as _};
+        if period < self.period() || period >= KES_PERIODS {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The key is at period {}, it can only be updated to a period up to {}",
+                    self.period(),
+                    KES_PERIODS - 1
+                ),
+            ));
+        }
+        let mut secret = crypto::SumEd25519_12::secret_from_binary(self.0.as_ref())
+            .map_err(|_| JsChainError::new(ErrorKind::InvalidKey, "Invalid kes secret key"))?;
+        for _ in self.period()..period {
+            crypto::SumEd25519_12::update(&mut secret).map_err(|_| {
+                JsChainError::new(ErrorKind::InvalidKey, "The kes secret key can't be updated")
+            })?;
+        }
+        self.0 = crypto::SecretKey::from_binary(secret.as_ref())
+            .map_err(|_| JsChainError::new(ErrorKind::InvalidKey, "Invalid kes secret key"))?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct VrfPublicKey(crypto::PublicKey<crypto::Curve25519_2HashDH>);
 
 impl From<crypto::PublicKey<crypto::Curve25519_2HashDH>> for VrfPublicKey {
@@ -1672,17 +2140,78 @@ impl From<crypto::PublicKey<crypto::Curve25519_2HashDH>> for VrfPublicKey {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl VrfPublicKey {
-    pub fn from_bech32(bech32_str: &str) -> Result<VrfPublicKey, JsValue> {
+    pub fn from_bech32(bech32_str: &str) -> Result<VrfPublicKey, JsChainError> {
         crypto::PublicKey::try_from_bech32_str(&bech32_str)
             .map(VrfPublicKey)
-            .map_err(|_| JsValue::from_str("Malformed vrf public key"))
+            .map_err(|_| JsChainError::new(ErrorKind::Bech32, "Malformed vrf public key"))
+    }
+
+    pub fn to_bech32(&self) -> String {
+        self.0.to_bech32_str()
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.0.as_ref().to_vec()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<VrfPublicKey, JsChainError> {
+        crypto::PublicKey::from_binary(bytes)
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::InvalidKey, "Invalid vrf public key", e)
+            })
+            .map(VrfPublicKey)
+    }
+}
+
+/// Verifiable random function key proving the leadership of a stake pool
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct VrfSecretKey(crypto::SecretKey<crypto::Curve25519_2HashDH>);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl VrfSecretKey {
+    pub fn generate() -> Result<VrfSecretKey, JsChainError> {
+        OsRng::new()
+            .map(crypto::SecretKey::<crypto::Curve25519_2HashDH>::generate)
+            .map(VrfSecretKey)
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::RandomGenerator,
+                    "Failed to generate the key",
+                    e,
+                )
+            })
+    }
+
+    pub fn to_public(&self) -> VrfPublicKey {
+        VrfPublicKey(self.0.to_public())
+    }
+
+    pub fn from_bech32(bech32_str: &str) -> Result<VrfSecretKey, JsChainError> {
+        crypto::SecretKey::try_from_bech32_str(&bech32_str)
+            .map(VrfSecretKey)
+            .map_err(|_| JsChainError::new(ErrorKind::Bech32, "Invalid vrf secret key"))
+    }
+
+    pub fn to_bech32(&self) -> String {
+        self.0.to_bech32_str()
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.0.as_ref().to_vec()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<VrfSecretKey, JsChainError> {
+        crypto::SecretKey::from_binary(bytes)
+            .map(VrfSecretKey)
+            .map_err(|_| JsChainError::new(ErrorKind::InvalidKey, "Invalid vrf secret key"))
     }
 }
 
 /// Amount of the balance in the transaction.
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Balance(tx::Balance);
 
 impl From<tx::Balance> for Balance {
@@ -1691,15 +2220,16 @@ impl From<tx::Balance> for Balance {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Balance {
     //Not sure is this is the best way
-    pub fn get_sign(&self) -> JsValue {
-        JsValue::from_str(match self.0 {
+    pub fn get_sign(&self) -> String {
+        match self.0 {
             tx::Balance::Positive(_) => "positive",
             tx::Balance::Negative(_) => "negative",
             tx::Balance::Zero => "zero",
-        })
+        }
+        .to_owned()
     }
 
     pub fn is_positive(&self) -> bool {
@@ -1735,10 +2265,10 @@ impl Balance {
 
 /// Algorithm used to compute transaction fees
 /// Currently the only implementation is the Linear one
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Fee(FeeVariant);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Fee {
     /// Linear algorithm, this is formed by: `coefficient * (#inputs + #outputs) + constant + certificate * #certificate
     pub fn linear_fee(constant: &Value, coefficient: &Value, certificate: &Value) -> Fee {
@@ -1776,7 +2306,7 @@ pub enum FeeVariant {
 ///
 /// It's important that witness works with opaque structures
 /// and may not know the contents of the internal transaction.
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct Witness(tx::Witness);
 
@@ -1786,7 +2316,7 @@ impl From<tx::Witness> for Witness {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Witness {
     /// Generate Witness for an utxo-based transaction Input
     pub fn for_utxo(
@@ -1862,21 +2392,26 @@ impl Witness {
     }
 
     /// Get string representation
-    pub fn to_bech32(&self) -> Result<String, JsValue> {
-        let bytes = self
-            .0
-            .serialize_as_vec()
-            .map_err(|error| JsValue::from_str(&format!("{}", error)))?;
+    pub fn to_bech32(&self) -> Result<String, JsChainError> {
+        let bytes = self.0.serialize_as_vec().map_err(|error| {
+            JsChainError::with_source(
+                ErrorKind::Serialization,
+                "Failed to serialize the witness",
+                error,
+            )
+        })?;
 
         Bech32::new("witness".to_string(), bytes.to_base32())
             .map(|bech32| bech32.to_string())
-            .map_err(|error| JsValue::from_str(&format!("{}", error)))
+            .map_err(|error| {
+                JsChainError::with_source(ErrorKind::Bech32, "Failed to encode the witness", error)
+            })
     }
 }
 
 impl_collection!(Witnesses, Witness);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct SpendingCounter(account::SpendingCounter);
 
 impl From<account::SpendingCounter> for SpendingCounter {
@@ -1891,7 +2426,7 @@ impl From<account::SpendingCounter> for SpendingCounter {
 /// the counter is incremented. A matching counter
 /// needs to be used in the spending phase to make
 /// sure we have non-replayability of a transaction.
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl SpendingCounter {
     pub fn zero() -> Self {
         account::SpendingCounter::zero().into()
@@ -1902,10 +2437,11 @@ impl SpendingCounter {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
 pub struct OldUtxoDeclaration(chain::legacy::UtxoDeclaration);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl OldUtxoDeclaration {
     pub fn size(&self) -> usize {
         self.0.addrs.len()
@@ -1920,8 +2456,10 @@ impl OldUtxoDeclaration {
     }
 }
 
+impl_collection!(OldUtxoDeclarations, OldUtxoDeclaration);
+
 /// All possible messages recordable in the Block content
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct Fragment(chain::fragment::Fragment);
 
@@ -1931,7 +2469,7 @@ impl From<chain::fragment::Fragment> for Fragment {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Fragment {
     pub fn from_transaction(tx: &Transaction) -> Fragment {
         use chain::fragment::Fragment as F;
@@ -1948,7 +2486,7 @@ impl Fragment {
     }
 
     /// Get a Transaction if the Fragment represents one
-    pub fn get_transaction(&self) -> Result<Transaction, JsValue> {
+    pub fn get_transaction(&self) -> Result<Transaction, JsChainError> {
         use chain::fragment::Fragment as F;
         use TaggedTransaction as T;
         match self.0.clone() {
@@ -1958,29 +2496,39 @@ impl Fragment {
             F::PoolRegistration(auth) => Ok(T::PoolRegistration(auth)),
             F::PoolRetirement(auth) => Ok(T::PoolRetirement(auth)),
             F::PoolUpdate(auth) => Ok(T::PoolUpdate(auth)),
-            _ => Err(JsValue::from_str("Invalid fragment type")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Invalid fragment type",
+            )),
         }
         .map(Transaction)
     }
 
-    pub fn get_old_utxo_declaration(&self) -> Result<OldUtxoDeclaration, JsValue> {
+    pub fn get_old_utxo_declaration(&self) -> Result<OldUtxoDeclaration, JsChainError> {
         match self.0.clone() {
             chain::fragment::Fragment::OldUtxoDeclaration(decl) => Ok(OldUtxoDeclaration(decl)),
-            _ => Err(JsValue::from_str("fragment is not OldUtxoDeclaration")),
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "fragment is not OldUtxoDeclaration",
+            )),
         }
     }
 
-    pub fn as_bytes(&self) -> Result<Vec<u8>, JsValue> {
-        self.0
-            .serialize_as_vec()
-            .map_err(|error| JsValue::from_str(&format!("{}", error)))
+    pub fn as_bytes(&self) -> Result<Vec<u8>, JsChainError> {
+        self.0.serialize_as_vec().map_err(|error| {
+            JsChainError::with_source(
+                ErrorKind::Serialization,
+                "Failed to serialize the fragment",
+                error,
+            )
+        })
     }
 
-    pub fn from_bytes(bytes: Uint8Array) -> Result<Fragment, JsValue> {
-        let mut slice: Box<[u8]> = vec![0; bytes.length() as usize].into_boxed_slice();
-        bytes.copy_to(&mut *slice);
-        chain::fragment::Fragment::deserialize(&*slice)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+    pub fn from_bytes(bytes: &[u8]) -> Result<Fragment, JsChainError> {
+        chain::fragment::Fragment::deserialize(bytes)
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid fragment", e)
+            })
             .map(Fragment)
     }
 
@@ -2062,7 +2610,7 @@ impl Fragment {
 /// `Block` is an element of the blockchain it contains multiple
 /// transaction and a reference to the parent block. Alongside
 /// with the position of that block in the chain.
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Block(chain::block::Block);
 
 impl From<chain::block::Block> for Block {
@@ -2071,17 +2619,25 @@ impl From<chain::block::Block> for Block {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Block {
     /// Deserialize a block from a byte array
-    pub fn from_bytes(bytes: Uint8Array) -> Result<Block, JsValue> {
-        let mut slice: Box<[u8]> = vec![0; bytes.length() as usize].into_boxed_slice();
-        bytes.copy_to(&mut *slice);
-        chain::block::Block::deserialize(&*slice)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+    pub fn from_bytes(bytes: &[u8]) -> Result<Block, JsChainError> {
+        chain::block::Block::deserialize(bytes)
+            .map_err(|e| JsChainError::with_source(ErrorKind::Deserialization, "Invalid block", e))
             .map(Block)
     }
 
+    pub fn as_bytes(&self) -> Result<Vec<u8>, JsChainError> {
+        self.0.serialize_as_vec().map_err(|error| {
+            JsChainError::with_source(
+                ErrorKind::Serialization,
+                "Failed to serialize the block",
+                error,
+            )
+        })
+    }
+
     pub fn id(&self) -> BlockId {
         self.0.id().into()
     }
@@ -2099,6 +2655,10 @@ impl Block {
             .into()
     }
 
+    pub fn date(&self) -> BlockDate {
+        self.0.date().into()
+    }
+
     pub fn epoch(&self) -> u32 {
         self.0.date().epoch
     }
@@ -2120,7 +2680,7 @@ impl Block {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct BlockId(key::Hash);
 
 impl From<key::Hash> for BlockId {
@@ -2129,17 +2689,17 @@ impl From<key::Hash> for BlockId {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl BlockId {
     pub fn calculate(bytes: &[u8]) -> Hash {
         key::Hash::hash_bytes(bytes).into()
     }
 
-    pub fn from_bytes(bytes: Uint8Array) -> Result<BlockId, JsValue> {
-        let mut slice: Box<[u8]> = vec![0; bytes.length() as usize].into_boxed_slice();
-        bytes.copy_to(&mut *slice);
-        key::Hash::deserialize(&*slice)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlockId, JsChainError> {
+        key::Hash::deserialize(bytes)
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid block id", e)
+            })
             .map(BlockId)
     }
 
@@ -2148,7 +2708,7 @@ impl BlockId {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct FragmentId(chain::fragment::FragmentId);
 
 impl From<chain::fragment::FragmentId> for FragmentId {
@@ -2157,17 +2717,17 @@ impl From<chain::fragment::FragmentId> for FragmentId {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl FragmentId {
     pub fn calculate(bytes: &[u8]) -> FragmentId {
         key::Hash::hash_bytes(bytes).into()
     }
 
-    pub fn from_bytes(bytes: Uint8Array) -> Result<FragmentId, JsValue> {
-        let mut slice: Box<[u8]> = vec![0; bytes.length() as usize].into_boxed_slice();
-        bytes.copy_to(&mut *slice);
-        chain::fragment::FragmentId::deserialize(&*slice)
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+    pub fn from_bytes(bytes: &[u8]) -> Result<FragmentId, JsChainError> {
+        chain::fragment::FragmentId::deserialize(bytes)
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Deserialization, "Invalid fragment id", e)
+            })
             .map(FragmentId)
     }
 
@@ -2178,12 +2738,13 @@ impl FragmentId {
 
 //this is useful for debugging, I'm not sure it is a good idea to have it here
 
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
-pub fn uint8array_to_hex(input: JsValue) -> Result<String, JsValue> {
+pub fn uint8array_to_hex(input: JsValue) -> Result<String, JsChainError> {
     //For some reason JSON.stringify serializes Uint8Array as objects instead of arrays
-    let input_array: std::collections::BTreeMap<usize, u8> = input
-        .into_serde()
-        .map_err(|e| JsValue::from_str(&format!("{}", e)))?;
+    let input_array: std::collections::BTreeMap<usize, u8> = input.into_serde().map_err(|e| {
+        JsChainError::with_source(ErrorKind::InvalidArgument, "Expected a Uint8Array", e)
+    })?;
 
     let mut s = String::with_capacity(input_array.len() * 2);
 
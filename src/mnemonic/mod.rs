@@ -0,0 +1,327 @@
+//! BIP39 mnemonic phrases
+//!
+//! Conversion between entropy and the human readable mnemonic phrases
+//! used to backup wallets, as specified in
+//! [BIP39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki).
+//! All the standard dictionaries are supported.
+//!
+//! Note that, unlike bitcoin, cardano wallets don't derive the root key from the
+//! BIP39 seed but directly from the entropy, see `Bip32PrivateKey::from_bip39_entropy`.
+
+use crate::{ErrorKind, JsChainError};
+use cryptoxide::digest::Digest as _;
+use cryptoxide::sha2::Sha256;
+use rand_os::rand_core::RngCore as _;
+use rand_os::OsRng;
+use std::fmt;
+use unicode_normalization::UnicodeNormalization as _;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// each word is an index in a dictionary of 2048 words
+const BITS_PER_WORD: usize = 11;
+
+/// the supported number of words in a mnemonic phrase
+const VALID_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    InvalidWordCount(usize),
+    InvalidEntropySize(usize),
+    UnknownWord(String),
+    InvalidChecksum,
+    UnknownLanguage,
+    RandomGenerator(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidWordCount(count) => write!(
+                f,
+                "Invalid number of words: {}, expected one of 12, 15, 18, 21 or 24",
+                count
+            ),
+            Error::InvalidEntropySize(size) => write!(
+                f,
+                "Invalid entropy size: {} bytes, expected one of 16, 20, 24, 28 or 32",
+                size
+            ),
+            Error::UnknownWord(word) => write!(f, "Word not in the dictionary: {}", word),
+            Error::InvalidChecksum => write!(f, "Invalid mnemonic checksum"),
+            Error::UnknownLanguage => {
+                write!(
+                    f,
+                    "The mnemonic phrase doesn't match any of the dictionaries"
+                )
+            }
+            Error::RandomGenerator(e) => write!(f, "Random generator failure: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for JsChainError {
+    fn from(error: Error) -> JsChainError {
+        let kind = match error {
+            Error::RandomGenerator(_) => ErrorKind::RandomGenerator,
+            _ => ErrorKind::Mnemonic,
+        };
+        JsChainError::new(kind, format!("{}", error))
+    }
+}
+
+/// Dictionaries from the BIP39 specification
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MnemonicLanguage {
+    English,
+    Japanese,
+    Korean,
+    Spanish,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Czech,
+    Portuguese,
+}
+
+impl MnemonicLanguage {
+    const ALL: [MnemonicLanguage; 10] = [
+        MnemonicLanguage::English,
+        MnemonicLanguage::Japanese,
+        MnemonicLanguage::Korean,
+        MnemonicLanguage::Spanish,
+        MnemonicLanguage::ChineseSimplified,
+        MnemonicLanguage::ChineseTraditional,
+        MnemonicLanguage::French,
+        MnemonicLanguage::Italian,
+        MnemonicLanguage::Czech,
+        MnemonicLanguage::Portuguese,
+    ];
+
+    fn dictionary(self) -> &'static str {
+        match self {
+            MnemonicLanguage::English => include_str!("dictionary/english.txt"),
+            MnemonicLanguage::Japanese => include_str!("dictionary/japanese.txt"),
+            MnemonicLanguage::Korean => include_str!("dictionary/korean.txt"),
+            MnemonicLanguage::Spanish => include_str!("dictionary/spanish.txt"),
+            MnemonicLanguage::ChineseSimplified => {
+                include_str!("dictionary/chinese_simplified.txt")
+            }
+            MnemonicLanguage::ChineseTraditional => {
+                include_str!("dictionary/chinese_traditional.txt")
+            }
+            MnemonicLanguage::French => include_str!("dictionary/french.txt"),
+            MnemonicLanguage::Italian => include_str!("dictionary/italian.txt"),
+            MnemonicLanguage::Czech => include_str!("dictionary/czech.txt"),
+            MnemonicLanguage::Portuguese => include_str!("dictionary/portuguese.txt"),
+        }
+    }
+
+    /// the japanese dictionary uses the ideographic space to separate words
+    fn separator(self) -> &'static str {
+        match self {
+            MnemonicLanguage::Japanese => "\u{3000}",
+            _ => " ",
+        }
+    }
+
+    fn word(self, index: u16) -> &'static str {
+        self.dictionary()
+            .lines()
+            .nth(index as usize)
+            .expect("dictionary index is always lower than 2048")
+    }
+
+    /// the word must be in NFKD, like the words of the specification
+    fn index_of(self, word: &str) -> Option<u16> {
+        self.dictionary()
+            .lines()
+            .position(|w| w.nfkd().eq(word.chars()))
+            .map(|index| index as u16)
+    }
+}
+
+/// BIP39 mnemonic phrase, it is kept in memory as its entropy
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+    language: MnemonicLanguage,
+}
+
+impl Mnemonic {
+    pub(crate) fn new(entropy: &[u8], language: MnemonicLanguage) -> Result<Self, Error> {
+        match entropy.len() {
+            16 | 20 | 24 | 28 | 32 => Ok(Mnemonic {
+                entropy: entropy.to_vec(),
+                language,
+            }),
+            size => Err(Error::InvalidEntropySize(size)),
+        }
+    }
+
+    pub(crate) fn parse(phrase: &str, language: MnemonicLanguage) -> Result<Self, Error> {
+        // accented letters can be typed composed (NFC) or not, the
+        // specification compares the words in their decomposed form (NFKD)
+        let phrase = phrase.nfkd().collect::<String>();
+        let indices = phrase
+            .split_whitespace()
+            .map(|word| {
+                language
+                    .index_of(word)
+                    .ok_or_else(|| Error::UnknownWord(word.to_owned()))
+            })
+            .collect::<Result<Vec<u16>, Error>>()?;
+
+        if !VALID_WORD_COUNTS.contains(&indices.len()) {
+            return Err(Error::InvalidWordCount(indices.len()));
+        }
+
+        // the concatenation of all the words' bits is the entropy followed by
+        // a checksum of `entropy bits / 32` bits
+        let checksum_bits = indices.len() * BITS_PER_WORD / 33;
+        let entropy_bits = checksum_bits * 32;
+
+        let mut bytes = Vec::with_capacity(entropy_bits / 8 + 1);
+        let mut accumulator: u32 = 0;
+        let mut accumulated_bits = 0;
+        for index in indices {
+            accumulator = (accumulator << BITS_PER_WORD) | u32::from(index);
+            accumulated_bits += BITS_PER_WORD;
+            while accumulated_bits >= 8 {
+                accumulated_bits -= 8;
+                bytes.push((accumulator >> accumulated_bits) as u8);
+            }
+            accumulator &= (1 << accumulated_bits) - 1;
+        }
+        // the remaining bits are always checksum bits, padding them to a
+        // full byte makes the comparison below simpler
+        if accumulated_bits > 0 {
+            bytes.push((accumulator << (8 - accumulated_bits)) as u8);
+        }
+
+        let entropy = &bytes[..entropy_bits / 8];
+        let checksum = bytes[entropy_bits / 8];
+        if checksum != checksum_byte(entropy, checksum_bits) {
+            return Err(Error::InvalidChecksum);
+        }
+
+        Mnemonic::new(entropy, language)
+    }
+
+    /// find the dictionary the given phrase belongs to
+    pub(crate) fn parse_any_language(phrase: &str) -> Result<Self, Error> {
+        MnemonicLanguage::ALL
+            .iter()
+            .filter_map(|language| Mnemonic::parse(phrase, *language).ok())
+            .next()
+            .ok_or(Error::UnknownLanguage)
+    }
+
+    pub(crate) fn random(word_count: usize, language: MnemonicLanguage) -> Result<Self, Error> {
+        if !VALID_WORD_COUNTS.contains(&word_count) {
+            return Err(Error::InvalidWordCount(word_count));
+        }
+        let mut entropy = vec![0u8; word_count * BITS_PER_WORD * 32 / 33 / 8];
+        OsRng::new()
+            .map_err(|e| Error::RandomGenerator(format!("{}", e)))?
+            .fill_bytes(&mut entropy);
+        Mnemonic::new(&entropy, language)
+    }
+
+    pub(crate) fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    fn indices(&self) -> Vec<u16> {
+        let checksum_bits = self.entropy.len() / 4;
+        let word_count = (self.entropy.len() * 8 + checksum_bits) / BITS_PER_WORD;
+
+        let mut indices = Vec::with_capacity(word_count);
+        let mut accumulator: u32 = 0;
+        let mut accumulated_bits = 0;
+        let checksum = checksum_byte(&self.entropy, checksum_bits);
+        for byte in self.entropy.iter().chain(std::iter::once(&checksum)) {
+            accumulator = (accumulator << 8) | u32::from(*byte);
+            accumulated_bits += 8;
+            if accumulated_bits >= BITS_PER_WORD {
+                accumulated_bits -= BITS_PER_WORD;
+                indices.push(((accumulator >> accumulated_bits) & 0x7ff) as u16);
+            }
+            accumulator &= (1 << accumulated_bits) - 1;
+        }
+        debug_assert_eq!(indices.len(), word_count);
+        indices
+    }
+}
+
+/// the first `bits` bits of the SHA256 of the entropy, the rest of the byte is zeroed
+fn checksum_byte(entropy: &[u8], bits: usize) -> u8 {
+    let mut hasher = Sha256::new();
+    let mut digest = [0u8; 32];
+    hasher.input(entropy);
+    hasher.result(&mut digest);
+    digest[0] & (0xff00u16 >> bits) as u8
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Mnemonic {
+    /// Generate a new random mnemonic phrase, valid word counts are 12, 15, 18, 21 and 24
+    /// ```javascript
+    /// const mnemonic = Mnemonic.generate(15, MnemonicLanguage.English);
+    /// ```
+    pub fn generate(
+        word_count: usize,
+        language: MnemonicLanguage,
+    ) -> Result<Mnemonic, JsChainError> {
+        Mnemonic::random(word_count, language).map_err(JsChainError::from)
+    }
+
+    /// Encode the given entropy (16, 20, 24, 28 or 32 bytes) as a mnemonic phrase
+    pub fn from_entropy(
+        entropy: &[u8],
+        language: MnemonicLanguage,
+    ) -> Result<Mnemonic, JsChainError> {
+        Mnemonic::new(entropy, language).map_err(JsChainError::from)
+    }
+
+    /// Parse a mnemonic phrase in the given language, this checks the checksum is valid
+    /// ```javascript
+    /// const mnemonic = Mnemonic.from_phrase(
+    ///     &#39;abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about&#39;,
+    ///     MnemonicLanguage.English
+    /// );
+    /// ```
+    pub fn from_phrase(phrase: &str, language: MnemonicLanguage) -> Result<Mnemonic, JsChainError> {
+        Mnemonic::parse(phrase, language).map_err(JsChainError::from)
+    }
+
+    /// Check if the phrase is valid in the given language (known words, count and checksum)
+    pub fn validate(phrase: &str, language: MnemonicLanguage) -> bool {
+        Mnemonic::parse(phrase, language).is_ok()
+    }
+
+    pub fn to_phrase(&self) -> String {
+        self.indices()
+            .into_iter()
+            .map(|index| self.language.word(index))
+            .collect::<Vec<&str>>()
+            .join(self.language.separator())
+    }
+
+    pub fn to_entropy(&self) -> Vec<u8> {
+        self.entropy.clone()
+    }
+
+    pub fn language(&self) -> MnemonicLanguage {
+        self.language
+    }
+
+    pub fn word_count(&self) -> usize {
+        (self.entropy.len() * 8 + self.entropy.len() / 4) / BITS_PER_WORD
+    }
+}
@@ -0,0 +1,279 @@
+//! Multisig accounts
+//!
+//! A multisig account is identified by the hash of its declaration: a
+//! threshold and a list of owners, each owner being either a public key or
+//! a nested declaration. Spending from the account needs the signatures of
+//! at least `threshold` owners, a nested declaration counts as one owner
+//! when its own threshold is reached.
+
+use crate::{
+    tx, Account, Address, AddressDiscrimination, ErrorKind, Hash, JsChainError, PrivateKey,
+    PublicKey, SpendingCounter, TransactionSignDataHash, Witness,
+};
+use chain::multisig;
+use chain_crypto as crypto;
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// maximum number of owners of a declaration
+const MAX_OWNERS: usize = 8;
+
+#[derive(Clone)]
+enum MultisigOwner {
+    Key(crypto::PublicKey<crypto::Ed25519>),
+    Sub(MultisigDeclaration),
+}
+
+/// Parameters of a multisig account
+///
+/// ```javascript
+/// const declaration = MultisigDeclaration.new(2);
+/// declaration.add_owner(alice_public_key);
+/// declaration.add_owner(bob_public_key);
+/// declaration.add_owner(carol_public_key);
+/// const address = declaration.to_address(AddressDiscrimination.Production);
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct MultisigDeclaration {
+    threshold: u8,
+    owners: Vec<MultisigOwner>,
+}
+
+impl MultisigDeclaration {
+    fn check(&self, nested: bool) -> Result<(), JsChainError> {
+        if self.owners.is_empty() || self.owners.len() > MAX_OWNERS {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                format!("A declaration has between 1 and {} owners", MAX_OWNERS),
+            ));
+        }
+        if self.threshold == 0 || usize::from(self.threshold) > self.owners.len() {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "The threshold must be between 1 and the number of owners",
+            ));
+        }
+        for owner in &self.owners {
+            if let MultisigOwner::Sub(sub) = owner {
+                if nested {
+                    return Err(JsChainError::new(
+                        ErrorKind::InvalidArgument,
+                        "Declarations can only be nested one level deep",
+                    ));
+                }
+                sub.check(true)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn to_declaration(&self) -> multisig::Declaration {
+        multisig::Declaration {
+            threshold: usize::from(self.threshold),
+            owners: self
+                .owners
+                .iter()
+                .map(|owner| match owner {
+                    MultisigOwner::Key(key) => multisig::DeclElement::from_publickey(key),
+                    MultisigOwner::Sub(sub) => multisig::DeclElement::Sub(sub.to_declaration()),
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn identifier(&self) -> Result<multisig::Identifier, JsChainError> {
+        self.check(false)?;
+        Ok(self.to_declaration().to_identifier())
+    }
+
+    /// position of the key in the declaration tree
+    fn index_of(&self, key: &crypto::PublicKey<crypto::Ed25519>) -> Option<Vec<u8>> {
+        self.owners
+            .iter()
+            .enumerate()
+            .find_map(|(index, owner)| match owner {
+                MultisigOwner::Key(owner) if owner == key => Some(vec![index as u8]),
+                MultisigOwner::Sub(sub) => sub.index_of(key).map(|mut path| {
+                    path.insert(0, index as u8);
+                    path
+                }),
+                _ => None,
+            })
+    }
+
+    fn is_satisfied(&self, signers: &[crypto::PublicKey<crypto::Ed25519>]) -> bool {
+        let signed = self
+            .owners
+            .iter()
+            .filter(|owner| match owner {
+                MultisigOwner::Key(key) => signers.contains(key),
+                MultisigOwner::Sub(sub) => sub.is_satisfied(signers),
+            })
+            .count();
+        signed >= usize::from(self.threshold)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl MultisigDeclaration {
+    /// Start a declaration needing the signatures of `threshold` owners
+    pub fn new(threshold: u8) -> MultisigDeclaration {
+        MultisigDeclaration {
+            threshold,
+            owners: Vec::new(),
+        }
+    }
+
+    pub fn add_owner(&mut self, key: &PublicKey) {
+        self.owners.push(MultisigOwner::Key(key.0.clone()));
+    }
+
+    /// Add a nested declaration as one owner, it can't have nested declarations itself
+    pub fn add_sub_declaration(&mut self, declaration: &MultisigDeclaration) {
+        self.owners.push(MultisigOwner::Sub(declaration.clone()));
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub fn size(&self) -> usize {
+        self.owners.len()
+    }
+
+    /// The multisig account, fails if the threshold or the owners are invalid
+    pub fn to_account(&self) -> Result<Account, JsChainError> {
+        Ok(Account(tx::AccountIdentifier::Multi(self.identifier()?)))
+    }
+
+    pub fn to_address(
+        &self,
+        discrimination: AddressDiscrimination,
+    ) -> Result<Address, JsChainError> {
+        let identifier = self.identifier()?;
+        Address::multisig_from_merkle_root(identifier.as_ref(), discrimination)
+    }
+}
+
+/// Signature of a transaction by one of the owners of a multisig account
+///
+/// Each owner signs on its own, the signatures are then gathered to make the witness
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct MultisigPartialSignature {
+    key: crypto::PublicKey<crypto::Ed25519>,
+    signature: crypto::Signature<tx::WitnessMultisigData, crypto::Ed25519>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl MultisigPartialSignature {
+    /// The spending counter is the one of the multisig account
+    pub fn sign(
+        genesis_hash: &Hash,
+        transaction_id: &TransactionSignDataHash,
+        account_spending_counter: &SpendingCounter,
+        secret_key: &PrivateKey,
+    ) -> MultisigPartialSignature {
+        let data = tx::WitnessMultisigData::new(
+            &genesis_hash.0,
+            &transaction_id.0,
+            &account_spending_counter.0,
+        );
+        MultisigPartialSignature {
+            key: secret_key.0.to_public(),
+            signature: secret_key.0.sign(&data),
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(self.key.clone())
+    }
+
+    /// The public key followed by the signature
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.key.as_ref().to_vec();
+        bytes.extend_from_slice(self.signature.as_ref());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<MultisigPartialSignature, JsChainError> {
+        if bytes.len() != 96 {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidLength,
+                format!(
+                    "Invalid partial signature length. Found {}, expected: 96",
+                    bytes.len()
+                ),
+            ));
+        }
+        let key = crypto::PublicKey::from_binary(&bytes[..32]).map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::InvalidKey,
+                "Invalid public key",
+                format!("{:?}", e),
+            )
+        })?;
+        let signature = crypto::Signature::from_binary(&bytes[32..]).map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::Deserialization,
+                "Invalid signature",
+                format!("{:?}", e),
+            )
+        })?;
+        Ok(MultisigPartialSignature { key, signature })
+    }
+}
+
+crate::impl_collection!(MultisigPartialSignatures, MultisigPartialSignature);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Witness {
+    /// Generate the witness of a multisig account input from the signatures of its owners
+    ///
+    /// ```javascript
+    /// const signatures = MultisigPartialSignatures.new();
+    /// signatures.add(MultisigPartialSignature.sign(genesis_hash, transaction_id, counter, alice_key));
+    /// signatures.add(MultisigPartialSignature.from_bytes(bob_signature_bytes));
+    /// const witness = Witness.for_multisig_account(declaration, signatures);
+    /// ```
+    pub fn for_multisig_account(
+        declaration: &MultisigDeclaration,
+        signatures: &MultisigPartialSignatures,
+    ) -> Result<Witness, JsChainError> {
+        declaration.check(false)?;
+        let mut builder = multisig::WitnessBuilder::new();
+        let mut signers = Vec::new();
+        for partial in &signatures.0 {
+            if signers.contains(&partial.key) {
+                continue;
+            }
+            let path = declaration.index_of(&partial.key).ok_or_else(|| {
+                JsChainError::new(
+                    ErrorKind::InvalidArgument,
+                    "The key is not an owner of the multisig account",
+                )
+            })?;
+            let index = |i: u8| {
+                multisig::Index::from_u8(i).ok_or_else(|| {
+                    JsChainError::new(ErrorKind::InvalidArgument, "Invalid owner index")
+                })
+            };
+            let tree_index = match path.as_slice() {
+                [i] => multisig::TreeIndex::D1(index(*i)?),
+                [i, j] => multisig::TreeIndex::D2(index(*i)?, index(*j)?),
+                _ => unreachable!("declarations are nested one level deep"),
+            };
+            builder.append(tree_index, partial.key.clone(), partial.signature.clone());
+            signers.push(partial.key.clone());
+        }
+        if !declaration.is_satisfied(&signers) {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "Not enough signatures to reach the threshold of the multisig account",
+            ));
+        }
+        Ok(Witness(tx::Witness::Multisig(builder.finalize())))
+    }
+}
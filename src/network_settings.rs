@@ -0,0 +1,198 @@
+//! Settings of a network, as declared in its genesis block
+//!
+//! The initial fragment of block0 holds the configuration parameters of the
+//! blockchain, the other fragments of block0 declare the initial funds.
+
+use crate::{
+    fee, AddressDiscrimination, Block, ErrorKind, Fee, FeeVariant, Hash, JsChainError,
+    OldUtxoDeclaration, OldUtxoDeclarations, Output, Outputs, PublicKey, PublicKeys,
+};
+use chain::chaintypes;
+use chain::config::ConfigParam;
+use chain::fragment::Fragment;
+use chain::key;
+use chain_core::property::Block as _;
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Leader election algorithm of the network
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConsensusVersion {
+    Bft,
+    GenesisPraos,
+}
+
+impl From<chaintypes::ConsensusVersion> for ConsensusVersion {
+    fn from(version: chaintypes::ConsensusVersion) -> ConsensusVersion {
+        match version {
+            chaintypes::ConsensusVersion::Bft => ConsensusVersion::Bft,
+            chaintypes::ConsensusVersion::GenesisPraos => ConsensusVersion::GenesisPraos,
+        }
+    }
+}
+
+impl From<ConsensusVersion> for chaintypes::ConsensusVersion {
+    fn from(version: ConsensusVersion) -> chaintypes::ConsensusVersion {
+        match version {
+            ConsensusVersion::Bft => chaintypes::ConsensusVersion::Bft,
+            ConsensusVersion::GenesisPraos => chaintypes::ConsensusVersion::GenesisPraos,
+        }
+    }
+}
+
+/// Settings of the network, read from its genesis block
+///
+/// ```javascript
+/// const settings = NetworkSettings.from_block0(Block.from_bytes(block0_bytes));
+/// const witness = Witness.for_account(
+///     settings.block0_hash(),
+///     transaction_id,
+///     private_key,
+///     spending_counter
+/// );
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct NetworkSettings {
+    block0_hash: key::Hash,
+    block0_date: u64,
+    discrimination: chain_addr::Discrimination,
+    fee: fee::LinearFee,
+    slot_duration: u8,
+    slots_per_epoch: u32,
+    consensus_version: chaintypes::ConsensusVersion,
+    kes_update_speed: Option<u32>,
+    bft_leaders: Vec<PublicKey>,
+    initial_funds: Vec<Output>,
+    legacy_funds: Vec<OldUtxoDeclaration>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl NetworkSettings {
+    /// Read the settings from the genesis block, fails if the block doesn't start
+    /// with the initial fragment or if a mandatory parameter is missing
+    pub fn from_block0(block0: &Block) -> Result<NetworkSettings, JsChainError> {
+        let mut fragments = block0.0.fragments();
+        let params = match fragments.next() {
+            Some(Fragment::Initial(params)) => params,
+            _ => {
+                return Err(JsChainError::new(
+                    ErrorKind::InvalidBlock,
+                    "Block0 must start with the initial fragment",
+                ))
+            }
+        };
+
+        let mut block0_date = None;
+        let mut discrimination = None;
+        let mut fee = None;
+        let mut slot_duration = None;
+        let mut slots_per_epoch = None;
+        let mut consensus_version = None;
+        let mut kes_update_speed = None;
+        let mut bft_leaders = Vec::new();
+        for param in params.iter() {
+            match param {
+                ConfigParam::Block0Date(date) => block0_date = Some(date.0),
+                ConfigParam::Discrimination(d) => discrimination = Some(*d),
+                ConfigParam::LinearFee(linear_fee) => fee = Some(*linear_fee),
+                ConfigParam::SlotDuration(duration) => slot_duration = Some(*duration),
+                ConfigParam::SlotsPerEpoch(slots) => slots_per_epoch = Some(*slots),
+                ConfigParam::ConsensusVersion(version) => consensus_version = Some(*version),
+                ConfigParam::KESUpdateSpeed(speed) => kes_update_speed = Some(*speed),
+                ConfigParam::AddBftLeader(leader) => {
+                    bft_leaders.push(PublicKey(leader.as_public_key().clone()))
+                }
+                _ => (),
+            }
+        }
+
+        let missing = |name: &str| {
+            JsChainError::new(
+                ErrorKind::InvalidBlock,
+                format!("Block0 has no {} parameter", name),
+            )
+        };
+
+        let mut initial_funds = Vec::new();
+        let mut legacy_funds = Vec::new();
+        for fragment in fragments {
+            match fragment {
+                Fragment::Transaction(transaction) => {
+                    initial_funds.extend(transaction.as_slice().outputs().iter().map(Output::from))
+                }
+                Fragment::OldUtxoDeclaration(declaration) => {
+                    legacy_funds.push(OldUtxoDeclaration(declaration.clone()))
+                }
+                _ => (),
+            }
+        }
+
+        Ok(NetworkSettings {
+            block0_hash: block0.0.id(),
+            block0_date: block0_date.ok_or_else(|| missing("block0 date"))?,
+            discrimination: discrimination.ok_or_else(|| missing("discrimination"))?,
+            // the ledger doesn't charge any fee if the parameter is not set
+            fee: fee.unwrap_or_else(|| fee::LinearFee::new(0, 0, 0)),
+            slot_duration: slot_duration.ok_or_else(|| missing("slot duration"))?,
+            slots_per_epoch: slots_per_epoch.ok_or_else(|| missing("slots per epoch"))?,
+            consensus_version: consensus_version.ok_or_else(|| missing("consensus version"))?,
+            kes_update_speed,
+            bft_leaders,
+            initial_funds,
+            legacy_funds,
+        })
+    }
+
+    /// Hash of the genesis block, needed to sign the transactions of the network
+    pub fn block0_hash(&self) -> Hash {
+        self.block0_hash.into()
+    }
+
+    /// Start time of the blockchain, in seconds since the unix epoch
+    pub fn block0_date(&self) -> f64 {
+        self.block0_date as f64
+    }
+
+    pub fn discrimination(&self) -> AddressDiscrimination {
+        self.discrimination.into()
+    }
+
+    /// Fee algorithm of the transactions, including the certificate fee
+    pub fn fee(&self) -> Fee {
+        Fee(FeeVariant::Linear(self.fee))
+    }
+
+    /// Duration of a slot in seconds
+    pub fn slot_duration(&self) -> u8 {
+        self.slot_duration
+    }
+
+    pub fn slots_per_epoch(&self) -> u32 {
+        self.slots_per_epoch
+    }
+
+    pub fn consensus_version(&self) -> ConsensusVersion {
+        self.consensus_version.into()
+    }
+
+    /// Maximum time in seconds between two updates of the KES key of the stake pools
+    pub fn kes_update_speed(&self) -> Option<u32> {
+        self.kes_update_speed
+    }
+
+    pub fn bft_leaders(&self) -> PublicKeys {
+        PublicKeys(self.bft_leaders.clone())
+    }
+
+    /// Outputs of the transactions of block0
+    pub fn initial_funds(&self) -> Outputs {
+        self.initial_funds.clone().into()
+    }
+
+    /// Legacy (byron) funds of block0
+    pub fn legacy_funds(&self) -> OldUtxoDeclarations {
+        self.legacy_funds.clone().into()
+    }
+}
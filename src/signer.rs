@@ -0,0 +1,377 @@
+//! Signing of a whole transaction in one pass
+//!
+//! A `Signer` is either a key held by the library or, in javascript, an
+//! object holding its keys elsewhere (hardware wallet, browser extension)
+//! with a `sign(kind, data)` method returning the 64 bytes of the signature,
+//! or a promise of them.
+
+use crate::{
+    account, key, tx, AccountBindingSignature, Bip32PrivateKey, CertificateKind, ErrorKind, Hash,
+    IndexSignatures, IndexedSignature, JsChainError, PayloadAuthData, PoolRegistrationAuthData,
+    PoolRetirementAuthData, PoolUpdateAuthData, PrivateKey, SpendingCounter,
+    StakeDelegationAuthData, Transaction, TransactionBuilderSetAuthData,
+    TransactionBuilderSetWitness, TransactionSignDataHash, Witness, Witnesses,
+};
+use chain_crypto as crypto;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// What the data given to a signer is
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureKind {
+    /// witness of an utxo input
+    Utxo,
+    /// witness of an account input
+    Account,
+    /// account binding signature of a certificate
+    AccountBinding,
+}
+
+#[derive(Clone)]
+enum SignerKey {
+    Key(key::EitherEd25519SecretKey),
+    /// spends the outputs of legacy (icarus) addresses
+    LegacyKey(crypto::SecretKey<crypto::Ed25519Bip32>),
+    #[cfg(feature = "wasm")]
+    External(JsValue),
+}
+
+/// Key signing the inputs or the certificate of a transaction
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct Signer(SignerKey);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Signer {
+    pub fn from_private_key(key: &PrivateKey) -> Signer {
+        Signer(SignerKey::Key(key.0.clone()))
+    }
+
+    /// Signer of the utxo inputs spending legacy (icarus) addresses
+    pub fn from_legacy_key(key: &Bip32PrivateKey) -> Signer {
+        Signer(SignerKey::LegacyKey(key.0.clone()))
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl Signer {
+    /// Signer calling `sign(kind, data)` on the given object, `kind` is a
+    /// `SignatureKind` and `data` the bytes to sign. Only usable with
+    /// `sign_transaction`
+    ///
+    /// ```javascript
+    /// const signer = Signer.from_js({
+    ///     sign: (kind, data) =&gt; hardwareWallet.signEd25519(path, data),
+    /// });
+    /// ```
+    pub fn from_js(signer: JsValue) -> Signer {
+        Signer(SignerKey::External(signer))
+    }
+}
+
+impl Signer {
+    fn sign_with_key(&self, kind: SignatureKind, data: &[u8]) -> Result<Vec<u8>, JsChainError> {
+        match &self.0 {
+            SignerKey::Key(key) => Ok(key.sign(&data).as_ref().to_vec()),
+            SignerKey::LegacyKey(key) if kind == SignatureKind::Utxo => {
+                Ok(key.sign(&data).as_ref().to_vec())
+            }
+            SignerKey::LegacyKey(_) => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Legacy keys only sign utxo inputs",
+            )),
+            #[cfg(feature = "wasm")]
+            SignerKey::External(_) => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "External signers are only supported by sign_transaction",
+            )),
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    async fn sign(&self, kind: SignatureKind, data: &[u8]) -> Result<Vec<u8>, JsChainError> {
+        let signer = match &self.0 {
+            SignerKey::External(signer) => signer,
+            _ => return self.sign_with_key(kind, data),
+        };
+        let external_error = |e: JsValue| {
+            JsChainError::with_source(
+                ErrorKind::Signer,
+                "The external signer failed",
+                e.as_string().unwrap_or_else(|| format!("{:?}", e)),
+            )
+        };
+        let sign: js_sys::Function = js_sys::Reflect::get(signer, &"sign".into())
+            .map_err(external_error)?
+            .dyn_into()
+            .map_err(|_| JsChainError::new(ErrorKind::Signer, "The signer has no sign function"))?;
+        let result = sign
+            .call2(
+                signer,
+                &JsValue::from(kind as u32),
+                &js_sys::Uint8Array::from(data),
+            )
+            .map_err(external_error)?;
+        let signature = wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&result))
+            .await
+            .map_err(external_error)?;
+        Ok(js_sys::Uint8Array::new(&signature).to_vec())
+    }
+}
+
+/// Signer of the certificate of a transaction
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct PayloadSigner {
+    signer: Signer,
+    /// index of the owner in the pool certificate, none for the delegating
+    /// account of a stake delegation
+    owner_index: Option<u8>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl PayloadSigner {
+    /// The delegating account of a stake delegation
+    pub fn account(signer: &Signer) -> PayloadSigner {
+        PayloadSigner {
+            signer: signer.clone(),
+            owner_index: None,
+        }
+    }
+
+    /// The owner at the given index in the owners of the pool, for the pool
+    /// certificates
+    pub fn pool_owner(signer: &Signer, owner_index: u8) -> PayloadSigner {
+        PayloadSigner {
+            signer: signer.clone(),
+            owner_index: Some(owner_index),
+        }
+    }
+}
+
+crate::impl_collection!(PayloadSigners, PayloadSigner);
+
+/// Signer of an input of a transaction
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct InputSigner {
+    signer: Signer,
+    spending_counter: Option<account::SpendingCounter>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl InputSigner {
+    pub fn utxo(signer: &Signer) -> InputSigner {
+        InputSigner {
+            signer: signer.clone(),
+            spending_counter: None,
+        }
+    }
+
+    /// The spending counter is the current one of the account
+    pub fn account(signer: &Signer, spending_counter: &SpendingCounter) -> InputSigner {
+        InputSigner {
+            signer: signer.clone(),
+            spending_counter: Some(spending_counter.0),
+        }
+    }
+}
+
+impl InputSigner {
+    fn kind(&self) -> SignatureKind {
+        match self.spending_counter {
+            Some(_) => SignatureKind::Account,
+            None => SignatureKind::Utxo,
+        }
+    }
+
+    fn data(&self, genesis_hash: &Hash, transaction_id: &TransactionSignDataHash) -> Vec<u8> {
+        match (&self.signer.0, self.spending_counter) {
+            (_, Some(spending_counter)) => {
+                tx::WitnessAccountData::new(&genesis_hash.0, &transaction_id.0, &spending_counter)
+                    .as_ref()
+                    .to_vec()
+            }
+            (SignerKey::LegacyKey(_), None) => {
+                tx::WitnessUtxoData::new(&genesis_hash.0, &transaction_id.0, true)
+                    .as_ref()
+                    .to_vec()
+            }
+            (_, None) => tx::WitnessUtxoData::new(&genesis_hash.0, &transaction_id.0, false)
+                .as_ref()
+                .to_vec(),
+        }
+    }
+
+    fn to_witness(&self, signature: &[u8]) -> Result<Witness, JsChainError> {
+        let invalid = |e: crypto::SignatureError| {
+            JsChainError::with_source(
+                ErrorKind::Deserialization,
+                "Invalid signature",
+                format!("{:?}", e),
+            )
+        };
+        let witness = match (&self.signer.0, self.spending_counter) {
+            (_, Some(_)) => {
+                tx::Witness::Account(crypto::Signature::from_binary(signature).map_err(invalid)?)
+            }
+            (SignerKey::LegacyKey(key), None) => tx::Witness::OldUtxo(
+                key.to_public(),
+                crypto::Signature::from_binary(signature).map_err(invalid)?,
+            ),
+            (_, None) => {
+                tx::Witness::Utxo(crypto::Signature::from_binary(signature).map_err(invalid)?)
+            }
+        };
+        Ok(Witness(witness))
+    }
+}
+
+crate::impl_collection!(InputSigners, InputSigner);
+
+/// Account binding signatures of the certificate, with the index of the
+/// owner for the pool certificates
+fn payload_auth(
+    builder: &TransactionBuilderSetAuthData,
+    mut signatures: Vec<(Option<u8>, AccountBindingSignature)>,
+) -> Result<PayloadAuthData, JsChainError> {
+    let kind = builder.certificate_kind();
+    let expected = match kind {
+        None | Some(CertificateKind::OwnerStakeDelegation) => signatures.is_empty(),
+        Some(CertificateKind::StakeDelegation) => {
+            signatures.len() == 1 && signatures[0].0.is_none()
+        }
+        Some(_) => !signatures.is_empty() && signatures.iter().all(|(index, _)| index.is_some()),
+    };
+    if !expected {
+        return Err(JsChainError::new(
+            ErrorKind::InvalidArgument,
+            "Wrong payload signers for the certificate of the transaction",
+        ));
+    }
+    signatures.sort_by_key(|(index, _)| *index);
+    if signatures.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return Err(JsChainError::new(
+            ErrorKind::InvalidArgument,
+            "The same pool owner is given twice",
+        ));
+    }
+    let indexed = || -> IndexSignatures {
+        signatures
+            .iter()
+            .filter_map(|(index, signature)| {
+                index.map(|index| IndexedSignature::new(index, signature))
+            })
+            .collect::<Vec<_>>()
+            .into()
+    };
+    Ok(match kind {
+        None => PayloadAuthData::for_no_payload(),
+        Some(CertificateKind::OwnerStakeDelegation) => {
+            PayloadAuthData::for_owner_stake_delegation()
+        }
+        Some(CertificateKind::StakeDelegation) => {
+            PayloadAuthData::for_stake_delegation(&StakeDelegationAuthData::new(&signatures[0].1))
+        }
+        Some(CertificateKind::PoolRegistration) => {
+            PayloadAuthData::for_pool_registration(&PoolRegistrationAuthData::new(&indexed())?)
+        }
+        Some(CertificateKind::PoolRetirement) => {
+            PayloadAuthData::for_pool_retirement(&PoolRetirementAuthData::new(&indexed())?)
+        }
+        Some(CertificateKind::PoolUpdate) => {
+            PayloadAuthData::for_pool_update(&PoolUpdateAuthData::new(&indexed())?)
+        }
+    })
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TransactionBuilderSetWitness {
+    /// Generate the witnesses and the payload auth with keys held by the
+    /// library, there is one input signer per input, in order
+    ///
+    /// ```javascript
+    /// const signers = InputSigners.new();
+    /// signers.add(InputSigner.utxo(Signer.from_private_key(utxo_key)));
+    /// signers.add(InputSigner.account(Signer.from_private_key(account_key), counter));
+    /// const transaction = builder.sign(genesis_hash, signers, PayloadSigners.new());
+    /// ```
+    pub fn sign(
+        self,
+        genesis_hash: &Hash,
+        input_signers: &InputSigners,
+        payload_signers: &PayloadSigners,
+    ) -> Result<Transaction, JsChainError> {
+        let transaction_id = self.get_auth_data_for_witness();
+        let witnesses = input_signers
+            .0
+            .iter()
+            .map(|input| {
+                let data = input.data(genesis_hash, &transaction_id);
+                input.to_witness(&input.signer.sign_with_key(input.kind(), &data)?)
+            })
+            .collect::<Result<Vec<_>, JsChainError>>()?;
+        let builder = self.set_witnesses(&Witnesses::from(witnesses));
+
+        let auth_data = builder.get_auth_data();
+        let signatures = payload_signers
+            .0
+            .iter()
+            .map(|payload| {
+                let signature = payload
+                    .signer
+                    .sign_with_key(SignatureKind::AccountBinding, &auth_data.as_bytes())?;
+                AccountBindingSignature::from_single_signature(&signature)
+                    .map(|signature| (payload.owner_index, signature))
+            })
+            .collect::<Result<Vec<_>, JsChainError>>()?;
+        let auth = payload_auth(&builder, signatures)?;
+        builder.set_payload_auth(&auth)
+    }
+}
+
+/// Generate the witnesses and the payload auth of the transaction, asking
+/// the external signers one signature at a time. There is one input signer
+/// per input, in order, and the payload signers are the delegating account
+/// for a stake delegation, or the signing owners of the pool for the pool
+/// certificates
+///
+/// ```javascript
+/// const signers = InputSigners.new();
+/// signers.add(InputSigner.utxo(Signer.from_js(ledger_signer)));
+/// const transaction = await sign_transaction(builder, genesis_hash, signers, PayloadSigners.new());
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub async fn sign_transaction(
+    builder: TransactionBuilderSetWitness,
+    genesis_hash: Hash,
+    input_signers: InputSigners,
+    payload_signers: PayloadSigners,
+) -> Result<Transaction, JsChainError> {
+    let transaction_id = builder.get_auth_data_for_witness();
+    let mut witnesses = Vec::with_capacity(input_signers.0.len());
+    for input in &input_signers.0 {
+        let data = input.data(&genesis_hash, &transaction_id);
+        let signature = input.signer.sign(input.kind(), &data).await?;
+        witnesses.push(input.to_witness(&signature)?);
+    }
+    let builder = builder.set_witnesses(&Witnesses::from(witnesses));
+
+    let auth_data = builder.get_auth_data().as_bytes();
+    let mut signatures = Vec::with_capacity(payload_signers.0.len());
+    for payload in &payload_signers.0 {
+        let signature = payload
+            .signer
+            .sign(SignatureKind::AccountBinding, &auth_data)
+            .await?;
+        signatures.push((
+            payload.owner_index,
+            AccountBindingSignature::from_single_signature(&signature)?,
+        ));
+    }
+    let auth = payload_auth(&builder, signatures)?;
+    builder.set_payload_auth(&auth)
+}
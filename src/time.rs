@@ -0,0 +1,178 @@
+//! Conversion between blockchain dates and wall-clock time
+//!
+//! Times are given to and returned from javascript as milliseconds since the
+//! unix epoch, the same unit as `Date.now()`.
+
+use crate::{ErrorKind, JsChainError, NetworkSettings, TimeOffsetSeconds};
+use chain::block;
+use chain_impl_mockchain as chain;
+use chain_time::{
+    DurationSeconds, Epoch, EpochPosition, EpochSlotOffset, SlotDuration, TimeEra, TimeFrame,
+    Timeline,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+fn to_millis(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as f64)
+        .unwrap_or(0.0)
+}
+
+fn from_millis(timestamp: f64) -> Result<SystemTime, JsChainError> {
+    if !timestamp.is_finite() || timestamp < 0.0 {
+        return Err(JsChainError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid timestamp: {}", timestamp),
+        ));
+    }
+    Ok(UNIX_EPOCH + Duration::from_millis(timestamp as u64))
+}
+
+/// Position of a slot in the blockchain: the epoch and the slot inside the epoch
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockDate(block::BlockDate);
+
+impl From<block::BlockDate> for BlockDate {
+    fn from(date: block::BlockDate) -> BlockDate {
+        BlockDate(date)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl BlockDate {
+    pub fn new(epoch: u32, slot: u32) -> BlockDate {
+        BlockDate(block::BlockDate {
+            epoch,
+            slot_id: slot,
+        })
+    }
+
+    pub fn epoch(&self) -> u32 {
+        self.0.epoch
+    }
+
+    pub fn slot(&self) -> u32 {
+        self.0.slot_id
+    }
+}
+
+/// Convert between block dates and timestamps (in milliseconds since the unix epoch)
+///
+/// ```javascript
+/// const converter = TimeConverter.from_network_settings(settings);
+/// const date = converter.block_date_at(Date.now());
+/// const start = new Date(converter.to_time(BlockDate.new(date.epoch() + 1, 0)));
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct TimeConverter {
+    block0_date: SystemTime,
+    slots_per_epoch: u32,
+    time_frame: TimeFrame,
+    era: TimeEra,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TimeConverter {
+    /// The block0 date is in seconds since the unix epoch, the slot duration in seconds
+    pub fn new(
+        block0_date: f64,
+        slot_duration: u8,
+        slots_per_epoch: u32,
+    ) -> Result<TimeConverter, JsChainError> {
+        if slot_duration == 0 || slots_per_epoch == 0 {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "Slot duration and slots per epoch must be greater than zero",
+            ));
+        }
+        let block0_date = from_millis(block0_date * 1000.0)?;
+        let time_frame = TimeFrame::new(
+            Timeline::new(block0_date),
+            SlotDuration::from_secs(u32::from(slot_duration)),
+        );
+        let era = TimeEra::new(0u64.into(), Epoch(0), slots_per_epoch);
+        Ok(TimeConverter {
+            block0_date,
+            slots_per_epoch,
+            time_frame,
+            era,
+        })
+    }
+
+    pub fn from_network_settings(
+        settings: &NetworkSettings,
+    ) -> Result<TimeConverter, JsChainError> {
+        TimeConverter::new(
+            settings.block0_date(),
+            settings.slot_duration(),
+            settings.slots_per_epoch(),
+        )
+    }
+
+    /// Start time of the given slot
+    pub fn to_time(&self, date: &BlockDate) -> Result<f64, JsChainError> {
+        if date.0.slot_id >= self.slots_per_epoch {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Slot {} is out of the epoch, there are {} slots per epoch",
+                    date.0.slot_id, self.slots_per_epoch
+                ),
+            ));
+        }
+        let slot = self.era.from_era_to_slot(EpochPosition {
+            epoch: Epoch(date.0.epoch),
+            slot: EpochSlotOffset(date.0.slot_id),
+        });
+        self.time_frame
+            .slot_to_systemtime(slot)
+            .map(to_millis)
+            .ok_or_else(|| {
+                JsChainError::new(
+                    ErrorKind::InvalidArgument,
+                    "Block date is too far in the future",
+                )
+            })
+    }
+
+    /// Slot in progress at the given time
+    pub fn block_date_at(&self, timestamp: f64) -> Result<BlockDate, JsChainError> {
+        let time = from_millis(timestamp)?;
+        self.time_frame
+            .slot_at(&time)
+            .and_then(|slot| self.era.from_slot_to_era(slot))
+            .map(|position| {
+                BlockDate(block::BlockDate {
+                    epoch: position.epoch.0,
+                    slot_id: position.slot.0,
+                })
+            })
+            .ok_or_else(|| {
+                JsChainError::new(
+                    ErrorKind::InvalidArgument,
+                    "Time is before the start of the blockchain",
+                )
+            })
+    }
+
+    /// Absolute time of an offset from the start of the blockchain, as used by
+    /// the pool retirement and update certificates
+    pub fn time_offset_to_time(&self, offset: &TimeOffsetSeconds) -> f64 {
+        to_millis(self.block0_date) + u64::from(offset.0) as f64 * 1000.0
+    }
+
+    /// Offset of the given time from the start of the blockchain, rounded down to the second
+    pub fn time_to_time_offset(&self, timestamp: f64) -> Result<TimeOffsetSeconds, JsChainError> {
+        let time = from_millis(timestamp)?;
+        let offset = time.duration_since(self.block0_date).map_err(|_| {
+            JsChainError::new(
+                ErrorKind::InvalidArgument,
+                "Time is before the start of the blockchain",
+            )
+        })?;
+        Ok(chain_time::timeline::TimeOffsetSeconds::from(DurationSeconds(offset.as_secs())).into())
+    }
+}
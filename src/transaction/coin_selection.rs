@@ -0,0 +1,402 @@
+use crate::{
+    tx, value, Address, ErrorKind, Fee, FeeVariant, Input, InputOutputBuilder, Inputs,
+    JsChainError, OutputPolicy, Outputs, Payload, Value,
+};
+use rand_os::rand_core::RngCore as _;
+use rand_os::OsRng;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// maximum number of inputs (and outputs) of a transaction
+const MAX_INPUTS: usize = 255;
+
+/// maximum number of branches explored by the branch and bound search
+const BRANCH_AND_BOUND_MAX_TRIES: usize = 100_000;
+
+/// Algorithm used to pick the inputs of a transaction
+///
+/// * LargestFirst: take the biggest inputs until the outputs and the fee are covered,
+///   this minimizes the number of inputs (and the fee)
+/// * RandomImprove: pick random inputs for every output, then try to make each output's
+///   selection closer to twice its value, so the change looks like a regular payment
+///   and the utxo set doesn't fragment into dust
+/// * BranchAndBound: look for a combination of inputs that pays the outputs and the fee
+///   exactly (at most the cost of a change output more), so no change is needed. This fails
+///   if there is no such combination
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoinSelectionStrategy {
+    LargestFirst,
+    RandomImprove,
+    BranchAndBound,
+}
+
+/// fee of a transaction with the given number of inputs and outputs
+type FeeFunction<'a> = dyn Fn(usize, usize) -> u64 + 'a;
+
+fn sum(values: &[u64], selected: &[usize]) -> u64 {
+    selected
+        .iter()
+        .fold(0u64, |total, index| total.saturating_add(values[*index]))
+}
+
+fn largest_first(
+    values: &[u64],
+    target: u64,
+    outputs: usize,
+    fee: &FeeFunction,
+) -> Option<Vec<usize>> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*b].cmp(&values[*a]));
+
+    let mut selected = Vec::new();
+    let mut total = 0u64;
+    for index in order {
+        selected.push(index);
+        total = total.saturating_add(values[index]);
+        if total >= target.saturating_add(fee(selected.len(), outputs)) {
+            return Some(selected);
+        }
+    }
+    None
+}
+
+/// random-improve as described in the
+/// [cardano wallet](https://iohk.io/en/blog/posts/2018/07/03/self-organisation-in-coin-selection/)
+fn random_improve(
+    values: &[u64],
+    output_values: &[u64],
+    fee: &FeeFunction,
+    random: &mut dyn FnMut(usize) -> usize,
+) -> Option<Vec<usize>> {
+    let target = output_values
+        .iter()
+        .fold(0u64, |total, value| total.saturating_add(*value));
+
+    let mut remaining: Vec<usize> = (0..values.len()).collect();
+    let mut selected = Vec::new();
+
+    let mut sorted_outputs = output_values.to_vec();
+    sorted_outputs.sort_by(|a, b| b.cmp(a));
+
+    // first phase: pick random inputs until each output is covered
+    let mut selections = Vec::with_capacity(sorted_outputs.len());
+    for output in sorted_outputs {
+        let mut selection = 0u64;
+        while selection < output {
+            if remaining.is_empty() {
+                // the random selection can't cover the outputs by itself, but the
+                // inputs may still be enough when taken in order
+                return largest_first(values, target, output_values.len(), fee);
+            }
+            let index = remaining.swap_remove(random(remaining.len()));
+            selection = selection.saturating_add(values[index]);
+            selected.push(index);
+        }
+        selections.push((output, selection));
+    }
+
+    // second phase: get each output's selection closer to twice its value,
+    // without going over three times its value
+    let distance = |a: u64, b: u64| if a > b { a - b } else { b - a };
+    for (output, selection) in selections.iter_mut() {
+        let ideal = output.saturating_mul(2);
+        let maximum = output.saturating_mul(3);
+        while !remaining.is_empty() {
+            let position = random(remaining.len());
+            let improved = selection.saturating_add(values[remaining[position]]);
+            if improved > maximum || distance(ideal, improved) >= distance(ideal, *selection) {
+                break;
+            }
+            selected.push(remaining.swap_remove(position));
+            *selection = improved;
+        }
+    }
+
+    // finally, make sure the fee is covered too
+    let mut total = sum(values, &selected);
+    while total < target.saturating_add(fee(selected.len(), output_values.len())) {
+        if remaining.is_empty() {
+            return None;
+        }
+        let index = remaining.swap_remove(random(remaining.len()));
+        total = total.saturating_add(values[index]);
+        selected.push(index);
+    }
+    Some(selected)
+}
+
+/// depth first search of the combination of inputs with the least excess over the
+/// target, within the given tolerance
+fn branch_and_bound(
+    values: &[u64],
+    target: u64,
+    outputs: usize,
+    tolerance: u64,
+    fee: &FeeFunction,
+) -> Option<Vec<usize>> {
+    // the fee is linear in the number of inputs, so each input is worth its value
+    // minus the fee it adds, inputs that don't pay for themselves are ignored
+    let input_cost = fee(1, outputs).saturating_sub(fee(0, outputs));
+    let target = target.saturating_add(fee(0, outputs));
+
+    let mut candidates: Vec<(usize, u64)> = values
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value > input_cost)
+        .map(|(index, value)| (index, value - input_cost))
+        .collect();
+    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+    // sum of the candidates from the given depth, used to prune the branches that
+    // can't reach the target anymore
+    let mut remaining_sums = vec![0u64; candidates.len() + 1];
+    for depth in (0..candidates.len()).rev() {
+        remaining_sums[depth] = remaining_sums[depth + 1].saturating_add(candidates[depth].1);
+    }
+
+    let mut best: Option<(u64, Vec<usize>)> = None;
+    let mut included: Vec<usize> = Vec::new();
+    let mut depth = 0;
+    let mut current = 0u64;
+
+    for _ in 0..BRANCH_AND_BOUND_MAX_TRIES {
+        let backtrack = if current.saturating_add(remaining_sums[depth]) < target
+            || current > target.saturating_add(tolerance)
+            || included.len() > MAX_INPUTS
+        {
+            true
+        } else if current >= target {
+            let excess = current - target;
+            if best
+                .as_ref()
+                .map_or(true, |(best_excess, _)| excess < *best_excess)
+            {
+                best = Some((excess, included.clone()));
+            }
+            // adding more inputs would only increase the excess
+            true
+        } else {
+            depth == candidates.len()
+        };
+
+        if let Some((0, _)) = best {
+            break;
+        }
+
+        if backtrack {
+            // explore the branch without the last included candidate
+            match included.pop() {
+                Some(last) => {
+                    current -= candidates[last].1;
+                    depth = last + 1;
+                }
+                None => break,
+            }
+        } else {
+            included.push(depth);
+            current += candidates[depth].1;
+            depth += 1;
+        }
+    }
+
+    best.map(|(_, included)| {
+        included
+            .into_iter()
+            .map(|depth| candidates[depth].0)
+            .collect()
+    })
+}
+
+/// Result of the coin selection: the inputs to use, and the resulting fee and change
+///
+/// ```javascript
+/// const selection = CoinSelection.select(
+///     CoinSelectionStrategy.RandomImprove,
+///     available_inputs,
+///     outputs,
+///     Payload.no_payload(),
+///     fee,
+///     OutputPolicy.one(change_address)
+/// );
+/// const ios = selection
+///     .to_builder()
+///     .seal_with_output_policy(Payload.no_payload(), fee, OutputPolicy.one(change_address));
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct CoinSelection {
+    inputs: Vec<tx::Input>,
+    outputs: Vec<tx::Output<chain_addr::Address>>,
+    fee: value::Value,
+    change: value::Value,
+    change_address: Option<chain_addr::Address>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl CoinSelection {
+    /// Pick inputs from the available ones to pay for the given outputs and the fee.
+    ///
+    /// If the policy is `OutputPolicy.one`, the excess is sent to the change address
+    /// unless it is smaller than the fee of the change output itself, in which case
+    /// it is left as extra fee.
+    pub fn select(
+        strategy: CoinSelectionStrategy,
+        available: &Inputs,
+        outputs: &Outputs,
+        payload: &Payload,
+        fee: &Fee,
+        policy: &OutputPolicy,
+    ) -> Result<CoinSelection, JsChainError> {
+        let fee_algorithm = match fee.0 {
+            FeeVariant::Linear(algorithm) => algorithm,
+        };
+        let fee_function = |inputs: usize, outputs: usize| -> u64 {
+            *payload
+                .calculate_fee(
+                    &fee_algorithm,
+                    inputs.min(MAX_INPUTS) as u8,
+                    outputs.min(MAX_INPUTS) as u8,
+                )
+                .as_ref()
+        };
+
+        let values: Vec<u64> = available
+            .0
+            .iter()
+            .map(|input| *input.0.value().as_ref())
+            .collect();
+        let output_values: Vec<u64> = outputs
+            .0
+            .iter()
+            .map(|output| *output.0.value.as_ref())
+            .collect();
+        let target = output_values
+            .iter()
+            .fold(0u64, |total, value| total.saturating_add(*value));
+
+        let selected = match strategy {
+            CoinSelectionStrategy::LargestFirst => {
+                largest_first(&values, target, output_values.len(), &fee_function)
+            }
+            CoinSelectionStrategy::RandomImprove => {
+                let mut rng = OsRng::new().map_err(|e| {
+                    JsChainError::with_source(
+                        ErrorKind::RandomGenerator,
+                        "Failed to initialize the random generator",
+                        e,
+                    )
+                })?;
+                let mut random = |bound: usize| rng.next_u32() as usize % bound;
+                random_improve(&values, &output_values, &fee_function, &mut random)
+            }
+            CoinSelectionStrategy::BranchAndBound => {
+                let tolerance = fee_function(0, output_values.len() + 1)
+                    .saturating_sub(fee_function(0, output_values.len()));
+                branch_and_bound(
+                    &values,
+                    target,
+                    output_values.len(),
+                    tolerance,
+                    &fee_function,
+                )
+            }
+        }
+        .ok_or_else(|| {
+            JsChainError::new(
+                ErrorKind::Balance,
+                "Not enough inputs to cover the outputs and the fee",
+            )
+        })?;
+
+        if selected.len() > MAX_INPUTS {
+            return Err(JsChainError::new(
+                ErrorKind::TransactionBuilder,
+                format!(
+                    "Too many inputs needed: {}, a transaction can have at most {}",
+                    selected.len(),
+                    MAX_INPUTS
+                ),
+            ));
+        }
+
+        let total = sum(&values, &selected);
+        let fee_without_change = fee_function(selected.len(), output_values.len());
+        let fee_with_change = fee_function(selected.len(), output_values.len() + 1);
+
+        let (change, change_address) = match &policy.0 {
+            tx::OutputPolicy::One(address) if total > target.saturating_add(fee_with_change) => {
+                (total - target - fee_with_change, Some(address.clone()))
+            }
+            _ => (0, None),
+        };
+        debug_assert!(total >= target.saturating_add(fee_without_change));
+
+        Ok(CoinSelection {
+            inputs: selected
+                .into_iter()
+                .map(|index| available.0[index].0.clone())
+                .collect(),
+            outputs: outputs.0.iter().map(|output| output.0.clone()).collect(),
+            fee: value::Value(total - target - change),
+            change: value::Value(change),
+            change_address,
+        })
+    }
+
+    /// The selected inputs
+    pub fn inputs(&self) -> Inputs {
+        self.inputs
+            .iter()
+            .cloned()
+            .map(Input)
+            .collect::<Vec<Input>>()
+            .into()
+    }
+
+    /// The fee paid by the transaction, including the excess that is not sent to
+    /// the change address
+    pub fn fee(&self) -> Value {
+        self.fee.into()
+    }
+
+    /// The value sent back to the change address, zero if there is no change output
+    pub fn change(&self) -> Value {
+        self.change.into()
+    }
+
+    pub fn change_address(&self) -> Option<Address> {
+        self.change_address.clone().map(Address)
+    }
+
+    /// Builder with the selected inputs and the requested outputs only, the change
+    /// output is not in it. The caller must seal the builder with the payload, fee
+    /// and output policy given to the selection, which adds the change back
+    ///
+    /// ```javascript
+    /// const ios = selection.to_builder().seal_with_output_policy(payload, fee, policy);
+    /// ```
+    pub fn to_builder(&self) -> Result<InputOutputBuilder, JsChainError> {
+        let mut builder = tx::InputOutputBuilder::empty();
+        for input in &self.inputs {
+            builder.add_input(input).map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::TransactionBuilder,
+                    "Failed to add the input",
+                    format!("{:?}", e),
+                )
+            })?;
+        }
+        for output in &self.outputs {
+            builder
+                .add_output(output.address.clone(), output.value)
+                .map_err(|e| {
+                    JsChainError::with_source(
+                        ErrorKind::TransactionBuilder,
+                        "Failed to add the output",
+                        format!("{:?}", e),
+                    )
+                })?;
+        }
+        Ok(InputOutputBuilder(builder))
+    }
+}
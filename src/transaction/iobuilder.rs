@@ -1,7 +1,8 @@
 use crate::{
-    certificate, tx, Address, Balance, Certificate, Fee, FeeVariant, Input, Inputs, OutputPolicy,
-    Outputs, Value,
+    certificate, fee, tx, value, Address, Balance, Certificate, ErrorKind, Fee, FeeVariant, Input,
+    Inputs, JsChainError, OutputPolicy, Outputs, Value,
 };
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 // Map payloads regardless of which type in a static way.
@@ -28,15 +29,15 @@ macro_rules! map_payload {
     };
 }
 
-#[wasm_bindgen]
-pub struct Payload(TaggedPayload);
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct Payload(pub(crate) TaggedPayload);
 
-enum TaggedPayload {
+pub(crate) enum TaggedPayload {
     NoPayload,
     Certificate(certificate::Certificate),
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Payload {
     pub fn no_payload() -> Self {
         Self(TaggedPayload::NoPayload)
@@ -47,35 +48,61 @@ impl Payload {
     }
 }
 
-#[wasm_bindgen]
-pub struct InputOutputBuilder(tx::InputOutputBuilder);
+impl Payload {
+    /// Fee of a transaction with this payload and the given number of inputs and outputs
+    pub(crate) fn calculate_fee(
+        &self,
+        fee_algorithm: &fee::LinearFee,
+        inputs: u8,
+        outputs: u8,
+    ) -> value::Value {
+        use fee::FeeAlgorithm as _;
+        use tx::Payload as _;
+        map_payload!(&self.0, |payload| fee_algorithm.calculate(
+            payload.payload_data().borrow().to_certificate_slice(),
+            inputs,
+            outputs
+        ))
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct InputOutputBuilder(pub(crate) tx::InputOutputBuilder);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl InputOutputBuilder {
     // TODO: Add constructor attribute
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn empty() -> InputOutputBuilder {
         InputOutputBuilder(tx::InputOutputBuilder::empty())
     }
 
     /// Add input to the IO Builder
-    #[wasm_bindgen]
-    pub fn add_input(&mut self, input: &Input) -> Result<(), JsValue> {
-        self.0
-            .add_input(&input.0)
-            .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn add_input(&mut self, input: &Input) -> Result<(), JsChainError> {
+        self.0.add_input(&input.0).map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::TransactionBuilder,
+                "Failed to add the input",
+                format!("{:?}", e),
+            )
+        })
     }
 
     /// Add output to the IO Builder
-    #[wasm_bindgen]
-    pub fn add_output(&mut self, address: &Address, value: &Value) -> Result<(), JsValue> {
-        self.0
-            .add_output(address.0.clone(), value.0)
-            .map_err(|e| JsValue::from_str(&format! {"{:?}", e}))
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn add_output(&mut self, address: &Address, value: &Value) -> Result<(), JsChainError> {
+        self.0.add_output(address.0.clone(), value.0).map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::TransactionBuilder,
+                "Failed to add the output",
+                format!("{:?}", e),
+            )
+        })
     }
 
     /// Estimate fee with the currently added inputs, outputs and certificate based on the given algorithm
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn estimate_fee(&self, fee: &Fee, payload: &Payload) -> Value {
         let fee_algorithm = match fee.0 {
             FeeVariant::Linear(fee_algorithm) => fee_algorithm,
@@ -87,8 +114,8 @@ impl InputOutputBuilder {
         ))
     }
 
-    #[wasm_bindgen]
-    pub fn get_balance(&self, payload: &Payload, fee: &Fee) -> Result<Balance, JsValue> {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_balance(&self, payload: &Payload, fee: &Fee) -> Result<Balance, JsChainError> {
         let fee_algorithm = match fee.0 {
             FeeVariant::Linear(fee_algorithm) => fee_algorithm,
         };
@@ -98,25 +125,29 @@ impl InputOutputBuilder {
             .get_balance(payload.payload_data().borrow(), &fee_algorithm));
 
         balance
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Balance, "Failed to compute the balance", e)
+            })
             .map(|balance| balance.into())
     }
 
-    #[wasm_bindgen]
-    pub fn get_balance_without_fee(&self) -> Result<Balance, JsValue> {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn get_balance_without_fee(&self) -> Result<Balance, JsChainError> {
         self.0
             .get_balance_without_fee()
             .map(|balance| balance.into())
-            .map_err(|e| JsValue::from_str(&format!("{}", e)))
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::Balance, "Failed to compute the balance", e)
+            })
     }
 
-    #[wasm_bindgen]
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
     pub fn build(self) -> InputOutput {
         InputOutput(self.0.build())
     }
     /// Seal the transaction by passing fee rule
-    #[wasm_bindgen]
-    pub fn seal(self, payload: &Payload, fee_algorithm: &Fee) -> Result<InputOutput, JsValue> {
+    #[cfg_attr(feature = "wasm", wasm_bindgen)]
+    pub fn seal(self, payload: &Payload, fee_algorithm: &Fee) -> Result<InputOutput, JsChainError> {
         use tx::Payload as _;
         let fee_algorithm = match fee_algorithm.0 {
             FeeVariant::Linear(algo) => algo,
@@ -125,7 +156,13 @@ impl InputOutputBuilder {
         map_payload!(&payload.0, |payload| self
             .0
             .seal(payload.payload_data().borrow(), &fee_algorithm))
-        .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        .map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::FeeAlgorithm,
+                "Failed to seal the transaction",
+                format!("{:?}", e),
+            )
+        })
         .map(InputOutput)
     }
 
@@ -141,7 +178,7 @@ impl InputOutputBuilder {
         payload: &Payload,
         fee_algorithm: &Fee,
         policy: &OutputPolicy,
-    ) -> Result<InputOutput, JsValue> {
+    ) -> Result<InputOutput, JsChainError> {
         use tx::Payload as _;
 
         let fee_algorithm = match fee_algorithm.0 {
@@ -152,15 +189,21 @@ impl InputOutputBuilder {
             &fee_algorithm,
             policy.0.clone(),
         ))
-        .map_err(|e| JsValue::from_str(&format!("{:?}", e)))
+        .map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::FeeAlgorithm,
+                "Failed to seal the transaction",
+                format!("{:?}", e),
+            )
+        })
         .map(|(_balance, _unassigned, io)| InputOutput(io))
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct InputOutput(tx::InputOutput);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl InputOutput {
     pub fn inputs(&self) -> Inputs {
         let v: Vec<_> = self.0.inputs.iter().map(|i| (*i).clone().into()).collect();
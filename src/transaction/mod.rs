@@ -1,19 +1,26 @@
+mod coin_selection;
 mod iobuilder;
+mod simple;
 mod txbuilder;
+mod unsigned;
 use super::certificate;
 use super::tx;
 use crate::{
     Certificate, Input, Inputs, Output, Outputs, TransactionSignDataHash, Witness, Witnesses,
 };
+pub use coin_selection::*;
 pub use iobuilder::*;
+pub use simple::*;
 pub use txbuilder::*;
+pub use unsigned::*;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 //-----------------------------------//
 //-------- Transaction --------------//
 //-----------------------------------//
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct Transaction(pub(crate) TaggedTransaction);
 
@@ -59,11 +66,11 @@ impl TaggedTransaction {
         )
     }
 
-    fn inputs(&self) -> Vec<tx::Input> {
+    pub(crate) fn inputs(&self) -> Vec<tx::Input> {
         map_payloads!(self, tx, tx.as_slice().inputs().iter().collect())
     }
 
-    fn outputs(&self) -> Vec<tx::Output<chain_addr::Address>> {
+    pub(crate) fn outputs(&self) -> Vec<tx::Output<chain_addr::Address>> {
         map_payloads!(self, tx, tx.as_slice().outputs().iter().collect())
     }
 
@@ -86,7 +93,7 @@ impl From<tx::Transaction<tx::NoExtra>> for Transaction {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Transaction {
     /// Get the transaction id, needed to compute its signature
     pub fn id(&self) -> TransactionSignDataHash {
@@ -0,0 +1,122 @@
+use crate::{
+    Account, Address, Certificate, ErrorKind, Fee, FeeVariant, Fragment, Hash, Input,
+    InputOutputBuilder, InputSigner, InputSigners, JsChainError, NetworkSettings, OutputPolicy,
+    Payload, PayloadSigners, PrivateKey, Signer, SpendingCounter, TransactionBuilder, UtxoPointer,
+    Value,
+};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Build and sign a transaction in one go, from the inputs with their keys
+///
+/// ```javascript
+/// const builder = SimpleTransactionBuilder.new(settings);
+/// builder.add_account_input(faucet_key, Value.from_str(&#39;1000&#39;), counter);
+/// builder.add_output(address, Value.from_str(&#39;800&#39;));
+/// const fragment = builder.finalize(OutputPolicy.one(faucet_address));
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SimpleTransactionBuilder {
+    genesis_hash: Hash,
+    fee: Fee,
+    iobuilder: InputOutputBuilder,
+    signers: InputSigners,
+    certificate: Option<Certificate>,
+    payload_signers: PayloadSigners,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl SimpleTransactionBuilder {
+    /// Use the genesis hash and the fees of the network
+    pub fn new(settings: &NetworkSettings) -> SimpleTransactionBuilder {
+        SimpleTransactionBuilder::with_parameters(&settings.block0_hash(), &settings.fee())
+    }
+
+    /// Same as `new`, when the settings are given by the node instead of the genesis block
+    pub fn with_parameters(genesis_hash: &Hash, fee: &Fee) -> SimpleTransactionBuilder {
+        let FeeVariant::Linear(fee) = fee.0;
+        SimpleTransactionBuilder {
+            genesis_hash: Hash(genesis_hash.0.clone()),
+            fee: Fee(FeeVariant::Linear(fee)),
+            iobuilder: InputOutputBuilder::empty(),
+            signers: InputSigners::new(),
+            certificate: None,
+            payload_signers: PayloadSigners::new(),
+        }
+    }
+
+    pub fn add_input(&mut self, input: &Input, signer: &InputSigner) -> Result<(), JsChainError> {
+        self.iobuilder.add_input(input)?;
+        self.signers.add(signer.clone());
+        Ok(())
+    }
+
+    pub fn add_utxo_input(
+        &mut self,
+        utxo_pointer: &UtxoPointer,
+        key: &PrivateKey,
+    ) -> Result<(), JsChainError> {
+        let signer = InputSigner::utxo(&Signer::from_private_key(key));
+        self.add_input(&Input::from_utxo(utxo_pointer), &signer)
+    }
+
+    /// Spend from the single account of the key, the spending counter is the
+    /// current one of the account
+    pub fn add_account_input(
+        &mut self,
+        key: &PrivateKey,
+        value: &Value,
+        spending_counter: &SpendingCounter,
+    ) -> Result<(), JsChainError> {
+        let account = Account::single_from_public_key(&key.to_public());
+        let signer = InputSigner::account(&Signer::from_private_key(key), spending_counter);
+        self.add_input(&Input::from_account(&account, value), &signer)
+    }
+
+    pub fn add_output(&mut self, address: &Address, value: &Value) -> Result<(), JsChainError> {
+        self.iobuilder.add_output(address, value)
+    }
+
+    /// Add a certificate to the transaction, the signers are the delegating
+    /// account for a stake delegation, or the signing owners of the pool for
+    /// the pool certificates
+    pub fn set_certificate(
+        &mut self,
+        certificate: &Certificate,
+        signers: &PayloadSigners,
+    ) -> Result<(), JsChainError> {
+        if self.certificate.is_some() {
+            return Err(JsChainError::new(
+                ErrorKind::TransactionBuilder,
+                "The transaction already has a certificate",
+            ));
+        }
+        self.certificate = Some(Certificate(certificate.0.clone()));
+        self.payload_signers = (0..signers.size())
+            .map(|index| signers.get(index))
+            .collect::<Vec<_>>()
+            .into();
+        Ok(())
+    }
+
+    /// Add the fee and the change following the policy, then sign the transaction
+    pub fn finalize(self, change_policy: &OutputPolicy) -> Result<Fragment, JsChainError> {
+        let payload = match &self.certificate {
+            Some(certificate) => Payload::certificate(certificate),
+            None => Payload::no_payload(),
+        };
+        let ios = self
+            .iobuilder
+            .seal_with_output_policy(&payload, &self.fee, change_policy)?;
+        let builder = match &self.certificate {
+            Some(certificate) => TransactionBuilder::new().payload(certificate),
+            None => TransactionBuilder::new().no_payload(),
+        };
+        let transaction = builder.set_ios(&ios.inputs(), &ios.outputs()).sign(
+            &self.genesis_hash,
+            &self.signers,
+            &self.payload_signers,
+        )?;
+        Ok(Fragment::from_transaction(&transaction))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 macro_rules! transition_from_to {
@@ -36,10 +37,10 @@ macro_rules! for_all_payloads {
 ///
 /// ```javascript
 /// ```
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TransactionBuilder(tx::TxBuilder);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TransactionBuilderSetIOs(TaggedTransactionBuilderSetIOs);
 
 enum TaggedTransactionBuilderSetIOs {
@@ -51,7 +52,7 @@ enum TaggedTransactionBuilderSetIOs {
     PoolUpdate(tx::TxBuilderState<tx::SetIOs<certificate::PoolUpdate>>),
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TransactionBuilderSetWitness(TaggedTransactionBuilderSetWitness);
 
 enum TaggedTransactionBuilderSetWitness {
@@ -63,7 +64,7 @@ enum TaggedTransactionBuilderSetWitness {
     PoolUpdate(tx::TxBuilderState<tx::SetWitnesses<certificate::PoolUpdate>>),
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TransactionBuilderSetAuthData(TaggedTransactionBuilderSetAuthData);
 
 enum TaggedTransactionBuilderSetAuthData {
@@ -75,9 +76,9 @@ enum TaggedTransactionBuilderSetAuthData {
     PoolUpdate(tx::TxBuilderState<tx::SetAuthData<certificate::PoolUpdate>>),
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TransactionBuilder {
-    #[wasm_bindgen(constructor)]
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
     pub fn new() -> Self {
         TransactionBuilder(tx::TxBuilder::new())
     }
@@ -109,7 +110,7 @@ impl TransactionBuilder {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TransactionBuilderSetIOs {
     pub fn set_ios(self, inputs: &Inputs, outputs: &Outputs) -> TransactionBuilderSetWitness {
         let inputs: Vec<_> = inputs.0.iter().map(|i| i.0.clone()).collect();
@@ -130,7 +131,7 @@ impl TransactionBuilderSetIOs {
 /// ```javascript
 /// ```
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TransactionBuilderSetWitness {
     pub fn get_auth_data_for_witness(&self) -> TransactionSignDataHash {
         let hash = for_all_payloads!(TaggedTransactionBuilderSetWitness, &self.0, |builder| {
@@ -154,7 +155,7 @@ impl TransactionBuilderSetWitness {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl TransactionBuilderSetAuthData {
     pub fn get_auth_data(&self) -> TransactionBindingAuthData {
         for_all_payloads!(TaggedTransactionBuilderSetAuthData, &self.0, |builder| {
@@ -162,8 +163,21 @@ impl TransactionBuilderSetAuthData {
         })
     }
 
+    /// Kind of the certificate of the transaction, the one of the payload auth to set
+    pub(crate) fn certificate_kind(&self) -> Option<CertificateKind> {
+        use TaggedTransactionBuilderSetAuthData as B;
+        match self.0 {
+            B::NoExtra(_) => None,
+            B::StakeDelegation(_) => Some(CertificateKind::StakeDelegation),
+            B::OwnerStakeDelegation(_) => Some(CertificateKind::OwnerStakeDelegation),
+            B::PoolRegistration(_) => Some(CertificateKind::PoolRegistration),
+            B::PoolRetirement(_) => Some(CertificateKind::PoolRetirement),
+            B::PoolUpdate(_) => Some(CertificateKind::PoolUpdate),
+        }
+    }
+
     /// Set the authenticated data
-    pub fn set_payload_auth(self, auth: &PayloadAuthData) -> Result<Transaction, JsValue> {
+    pub fn set_payload_auth(self, auth: &PayloadAuthData) -> Result<Transaction, JsChainError> {
         use super::TaggedTransaction as T;
         use TaggedPayloadAuthData as P;
 
@@ -172,47 +186,85 @@ impl TransactionBuilderSetAuthData {
                 TaggedTransactionBuilderSetAuthData::NoExtra(builder) => {
                     T::NoExtra(builder.set_payload_auth(&a))
                 }
-                _ => return Err(JsValue::from_str(&"Invalid auth type".to_owned())),
+                _ => {
+                    return Err(JsChainError::new(
+                        ErrorKind::WrongPayloadAuth,
+                        "Invalid auth type",
+                    ))
+                }
             },
             P::StakeDelegation(a) => match self.0 {
                 TaggedTransactionBuilderSetAuthData::StakeDelegation(builder) => {
                     T::StakeDelegation(builder.set_payload_auth(&a))
                 }
-                _ => return Err(JsValue::from_str(&"Invalid auth type".to_owned())),
+                _ => {
+                    return Err(JsChainError::new(
+                        ErrorKind::WrongPayloadAuth,
+                        "Invalid auth type",
+                    ))
+                }
             },
             P::OwnerStakeDelegation(a) => match self.0 {
                 TaggedTransactionBuilderSetAuthData::OwnerStakeDelegation(builder) => {
                     T::OwnerStakeDelegation(builder.set_payload_auth(&a))
                 }
-                _ => return Err(JsValue::from_str(&"Invalid auth type".to_owned())),
+                _ => {
+                    return Err(JsChainError::new(
+                        ErrorKind::WrongPayloadAuth,
+                        "Invalid auth type",
+                    ))
+                }
             },
             P::PoolRegistration(a) => match self.0 {
                 TaggedTransactionBuilderSetAuthData::PoolRegistration(builder) => {
                     T::PoolRegistration(builder.set_payload_auth(&a))
                 }
-                _ => return Err(JsValue::from_str(&"Invalid auth type".to_owned())),
+                _ => {
+                    return Err(JsChainError::new(
+                        ErrorKind::WrongPayloadAuth,
+                        "Invalid auth type",
+                    ))
+                }
             },
             P::PoolRetirement(a) => match self.0 {
                 TaggedTransactionBuilderSetAuthData::PoolRetirement(builder) => {
                     T::PoolRetirement(builder.set_payload_auth(&a))
                 }
-                _ => return Err(JsValue::from_str(&"Invalid auth type".to_owned())),
+                _ => {
+                    return Err(JsChainError::new(
+                        ErrorKind::WrongPayloadAuth,
+                        "Invalid auth type",
+                    ))
+                }
             },
             P::PoolUpdate(a) => match self.0 {
                 TaggedTransactionBuilderSetAuthData::PoolUpdate(builder) => {
                     T::PoolUpdate(builder.set_payload_auth(&a))
                 }
-                _ => return Err(JsValue::from_str(&"Invalid auth type".to_owned())),
+                _ => {
+                    return Err(JsChainError::new(
+                        ErrorKind::WrongPayloadAuth,
+                        "Invalid auth type",
+                    ))
+                }
             },
         };
         Ok(Transaction(tx))
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct TransactionBindingAuthData(Vec<u8>);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl TransactionBindingAuthData {
+    /// The bytes signed by the account binding signatures
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PayloadAuthData(TaggedPayloadAuthData);
 
 pub enum TaggedPayloadAuthData {
@@ -224,7 +276,7 @@ pub enum TaggedPayloadAuthData {
     PoolUpdate(<certificate::PoolUpdate as tx::Payload>::Auth),
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PayloadAuthData {
     pub fn for_no_payload() -> PayloadAuthData {
         Self(TaggedPayloadAuthData::NoPayload(()))
@@ -243,7 +295,7 @@ impl PayloadAuthData {
     }
 
     pub fn for_pool_retirement(auth_data: &PoolRetirementAuthData) -> PayloadAuthData {
-        Self(TaggedPayloadAuthData::PoolRegistration(auth_data.0.clone()))
+        Self(TaggedPayloadAuthData::PoolRetirement(auth_data.0.clone()))
     }
 
     pub fn for_pool_update(auth_data: &PoolUpdateAuthData) -> PayloadAuthData {
@@ -251,17 +303,17 @@ impl PayloadAuthData {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct StakeDelegationAuthData(<certificate::StakeDelegation as tx::Payload>::Auth);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl StakeDelegationAuthData {
     pub fn new(signature: &AccountBindingSignature) -> StakeDelegationAuthData {
         StakeDelegationAuthData(signature.0.clone())
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct IndexedSignature {
     index: u8,
@@ -270,7 +322,7 @@ pub struct IndexedSignature {
 
 crate::impl_collection!(IndexSignatures, IndexedSignature);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl IndexedSignature {
     pub fn new(index: u8, signature: &AccountBindingSignature) -> IndexedSignature {
         Self {
@@ -280,11 +332,11 @@ impl IndexedSignature {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone)]
 pub struct AccountBindingSignature(tx::AccountBindingSignature);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl AccountBindingSignature {
     pub fn new_single(private_key: &PrivateKey, auth_data: &TransactionBindingAuthData) -> Self {
         Self(tx::AccountBindingSignature::new_single(
@@ -292,24 +344,38 @@ impl AccountBindingSignature {
             &tx::TransactionBindingAuthData(auth_data.0.as_slice()),
         ))
     }
+
+    /// Signature of the auth data generated externally (such as hardware wallets)
+    pub fn from_single_signature(bytes: &[u8]) -> Result<AccountBindingSignature, JsChainError> {
+        crypto::Signature::from_binary(bytes)
+            .map(|signature| Self(tx::AccountBindingSignature::Single(signature)))
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::Deserialization,
+                    "Invalid account binding signature",
+                    format!("{:?}", e),
+                )
+            })
+    }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PoolRegistrationAuthData(<certificate::PoolRegistration as tx::Payload>::Auth);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolRegistrationAuthData {
-    pub fn new(signatures: &IndexSignatures) -> Result<PoolRegistrationAuthData, JsValue> {
+    pub fn new(signatures: &IndexSignatures) -> Result<PoolRegistrationAuthData, JsChainError> {
         signatures
             .0
             .iter()
             .map(|IndexedSignature { index, signature }| match &signature.0 {
                 tx::AccountBindingSignature::Single(s) => Ok((*index, s.clone())),
-                tx::AccountBindingSignature::Multi(_) => {
-                    Err(JsValue::from_str("Expected single account signature"))
-                }
+                tx::AccountBindingSignature::Multi(_) => Err(JsChainError::new(
+                    ErrorKind::WrongType,
+                    "Expected single account signature",
+                )),
             })
-            .collect::<Result<_, JsValue>>()
+            .collect::<Result<_, JsChainError>>()
             .map(|signatures| {
                 Self(certificate::PoolSignature::Owners(
                     certificate::PoolOwnersSigned { signatures },
@@ -318,22 +384,23 @@ impl PoolRegistrationAuthData {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PoolRetirementAuthData(<certificate::PoolRetirement as tx::Payload>::Auth);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolRetirementAuthData {
-    pub fn new(signatures: &IndexSignatures) -> Result<PoolRetirementAuthData, JsValue> {
+    pub fn new(signatures: &IndexSignatures) -> Result<PoolRetirementAuthData, JsChainError> {
         signatures
             .0
             .iter()
             .map(|IndexedSignature { index, signature }| match &signature.0 {
                 tx::AccountBindingSignature::Single(s) => Ok((*index, s.clone())),
-                tx::AccountBindingSignature::Multi(_) => {
-                    Err(JsValue::from_str("Expected single account signature"))
-                }
+                tx::AccountBindingSignature::Multi(_) => Err(JsChainError::new(
+                    ErrorKind::WrongType,
+                    "Expected single account signature",
+                )),
             })
-            .collect::<Result<_, JsValue>>()
+            .collect::<Result<_, JsChainError>>()
             .map(|signatures| {
                 Self(certificate::PoolSignature::Owners(
                     certificate::PoolOwnersSigned { signatures },
@@ -342,22 +409,23 @@ impl PoolRetirementAuthData {
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct PoolUpdateAuthData(<certificate::PoolUpdate as tx::Payload>::Auth);
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl PoolUpdateAuthData {
-    pub fn new(signatures: &IndexSignatures) -> Result<PoolUpdateAuthData, JsValue> {
+    pub fn new(signatures: &IndexSignatures) -> Result<PoolUpdateAuthData, JsChainError> {
         signatures
             .0
             .iter()
             .map(|IndexedSignature { index, signature }| match &signature.0 {
                 tx::AccountBindingSignature::Single(s) => Ok((*index, s.clone())),
-                tx::AccountBindingSignature::Multi(_) => {
-                    Err(JsValue::from_str("Expected single account signature"))
-                }
+                tx::AccountBindingSignature::Multi(_) => Err(JsChainError::new(
+                    ErrorKind::WrongType,
+                    "Expected single account signature",
+                )),
             })
-            .collect::<Result<_, JsValue>>()
+            .collect::<Result<_, JsChainError>>()
             .map(|signatures| {
                 Self(certificate::PoolSignature::Owners(
                     certificate::PoolOwnersSigned { signatures },
@@ -0,0 +1,533 @@
+use super::iobuilder::TaggedPayload;
+use crate::*;
+use bech32::FromBase32 as _;
+use chain::legacy;
+use chain_core::mempack::ReadError;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// bech32 prefix of the encoded unsigned transactions
+const BECH32_PREFIX: &str = "unsigned_tx";
+
+/// version of the binary encoding
+const ENCODING_VERSION: u8 = 1;
+
+/// maximum number of inputs or outputs of a transaction
+const MAX_IOS: usize = 255;
+
+/// Kind of the witness an input needs
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigningRequirementKind {
+    Utxo,
+    LegacyUtxo,
+    Account,
+}
+
+#[derive(Clone)]
+enum Requirement {
+    /// the address of the spent output, if known
+    Utxo(Option<chain_addr::Address>),
+    /// the base58 address of the spent output, if known
+    LegacyUtxo(Option<legacy::OldAddress>),
+    /// the account is the one of the input
+    Account,
+}
+
+/// What is needed to sign an input of an `UnsignedTransaction`
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct SigningRequirement {
+    requirement: Requirement,
+    input: tx::Input,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl SigningRequirement {
+    pub fn kind(&self) -> SigningRequirementKind {
+        match self.requirement {
+            Requirement::Utxo(_) => SigningRequirementKind::Utxo,
+            Requirement::LegacyUtxo(_) => SigningRequirementKind::LegacyUtxo,
+            Requirement::Account => SigningRequirementKind::Account,
+        }
+    }
+
+    /// Address of the spent output, whose spending key signs the input
+    pub fn address(&self) -> Option<Address> {
+        match &self.requirement {
+            Requirement::Utxo(address) => address.clone().map(Address),
+            _ => None,
+        }
+    }
+
+    /// Base58 address of the spent legacy output
+    pub fn legacy_address(&self) -> Option<String> {
+        match &self.requirement {
+            Requirement::LegacyUtxo(address) => {
+                address.as_ref().map(|address| format!("{}", address))
+            }
+            _ => None,
+        }
+    }
+
+    /// Account of the input, signed by its key or by its owners for a multisig account
+    pub fn account(&self) -> Option<AccountIdentifier> {
+        match self.input.to_enum() {
+            tx::InputEnum::AccountInput(account, _) => Some(AccountIdentifier(account)),
+            tx::InputEnum::UtxoInput(_) => None,
+        }
+    }
+}
+
+/// Transaction waiting for the witnesses of its inputs
+///
+/// It can be saved or sent to the other signers as bytes or bech32, and
+/// turned into a `Transaction` once every input has its witness.
+///
+/// ```javascript
+/// const unsigned = UnsignedTransaction.new(payload, ios.inputs(), ios.outputs());
+/// unsigned.set_utxo_address(0, address);
+/// const encoded = unsigned.to_bech32();
+///
+/// // on the signing machine
+/// const received = UnsignedTransaction.from_bech32(encoded);
+/// received.add_witness(0, Witness.for_utxo(genesis_hash, received.id(), private_key));
+///
+/// const transaction = received.finalize(PayloadAuthData.for_no_payload());
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct UnsignedTransaction {
+    payload: Option<certificate::Certificate>,
+    inputs: Vec<Input>,
+    outputs: Vec<Output>,
+    requirements: Vec<Requirement>,
+    witnesses: Vec<Option<Witness>>,
+}
+
+impl UnsignedTransaction {
+    fn builder(&self) -> TransactionBuilderSetWitness {
+        let builder = match &self.payload {
+            Some(certificate) => {
+                TransactionBuilder::new().payload(&Certificate(certificate.clone()))
+            }
+            None => TransactionBuilder::new().no_payload(),
+        };
+        builder.set_ios(&self.inputs.clone().into(), &self.outputs.clone().into())
+    }
+
+    fn complete_builder(&self) -> Result<TransactionBuilderSetAuthData, JsChainError> {
+        let witnesses = self
+            .witnesses
+            .iter()
+            .cloned()
+            .collect::<Option<Vec<Witness>>>()
+            .ok_or_else(|| {
+                JsChainError::new(
+                    ErrorKind::TransactionBuilder,
+                    "Some inputs don't have their witness yet",
+                )
+            })?;
+        Ok(self.builder().set_witnesses(&witnesses.into()))
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), JsChainError> {
+        if index >= self.inputs.len() {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Input index {} is out of bounds, there are {} inputs",
+                    index,
+                    self.inputs.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn set_utxo_requirement(
+        &mut self,
+        index: usize,
+        requirement: Requirement,
+    ) -> Result<(), JsChainError> {
+        self.check_index(index)?;
+        if let Requirement::Account = self.requirements[index] {
+            return Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Input is not from utxo",
+            ));
+        }
+        self.requirements[index] = requirement;
+        self.witnesses[index] = None;
+        Ok(())
+    }
+
+    fn check_witness(&self, index: usize, witness: &tx::Witness) -> Result<(), JsChainError> {
+        let matches = match (
+            &self.requirements[index],
+            witness,
+            self.inputs[index].0.to_enum(),
+        ) {
+            (Requirement::Utxo(_), tx::Witness::Utxo(_), _) => true,
+            (Requirement::LegacyUtxo(_), tx::Witness::OldUtxo(..), _) => true,
+            (Requirement::Account, tx::Witness::Account(_), tx::InputEnum::AccountInput(id, _)) => {
+                id.to_single_account().is_some()
+            }
+            (
+                Requirement::Account,
+                tx::Witness::Multisig(_),
+                tx::InputEnum::AccountInput(id, _),
+            ) => id.to_multi_account().is_some(),
+            _ => false,
+        };
+        if !matches {
+            return Err(JsChainError::new(
+                ErrorKind::WrongType,
+                format!("The witness doesn't match the kind of the input {}", index),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl UnsignedTransaction {
+    pub fn new(
+        payload: &Payload,
+        inputs: &Inputs,
+        outputs: &Outputs,
+    ) -> Result<UnsignedTransaction, JsChainError> {
+        if inputs.0.len() > MAX_IOS || outputs.0.len() > MAX_IOS {
+            return Err(JsChainError::new(
+                ErrorKind::TransactionBuilder,
+                format!(
+                    "A transaction has at most {} inputs and {} outputs",
+                    MAX_IOS, MAX_IOS
+                ),
+            ));
+        }
+        let payload = match &payload.0 {
+            TaggedPayload::NoPayload => None,
+            TaggedPayload::Certificate(certificate) => Some(certificate.clone()),
+        };
+        let requirements = inputs
+            .0
+            .iter()
+            .map(|input| match input.0.get_type() {
+                tx::InputType::Utxo => Requirement::Utxo(None),
+                tx::InputType::Account => Requirement::Account,
+            })
+            .collect();
+        Ok(UnsignedTransaction {
+            payload,
+            inputs: inputs.0.clone(),
+            outputs: outputs.0.clone(),
+            requirements,
+            witnesses: vec![None; inputs.0.len()],
+        })
+    }
+
+    /// The data signed by the witnesses
+    pub fn id(&self) -> TransactionSignDataHash {
+        self.builder().get_auth_data_for_witness()
+    }
+
+    pub fn certificate(&self) -> Option<Certificate> {
+        self.payload.clone().map(Certificate)
+    }
+
+    pub fn inputs(&self) -> Inputs {
+        self.inputs.clone().into()
+    }
+
+    pub fn outputs(&self) -> Outputs {
+        self.outputs.clone().into()
+    }
+
+    pub fn requirement(&self, index: usize) -> Result<SigningRequirement, JsChainError> {
+        self.check_index(index)?;
+        Ok(SigningRequirement {
+            requirement: self.requirements[index].clone(),
+            input: self.inputs[index].0.clone(),
+        })
+    }
+
+    /// Record the address of the output spent by an utxo input, for the signers to find their key
+    pub fn set_utxo_address(
+        &mut self,
+        index: usize,
+        address: &Address,
+    ) -> Result<(), JsChainError> {
+        self.set_utxo_requirement(index, Requirement::Utxo(Some(address.0.clone())))
+    }
+
+    /// Mark an utxo input as spending a legacy (byron) output at the given base58 address
+    pub fn set_legacy_utxo_address(
+        &mut self,
+        index: usize,
+        address: &str,
+    ) -> Result<(), JsChainError> {
+        let address = address.parse::<legacy::OldAddress>().map_err(|e| {
+            JsChainError::with_source(ErrorKind::InvalidAddress, "Invalid legacy address", e)
+        })?;
+        self.set_utxo_requirement(index, Requirement::LegacyUtxo(Some(address)))
+    }
+
+    /// Set the witness of an input, replacing the previous one if any
+    pub fn add_witness(&mut self, index: usize, witness: &Witness) -> Result<(), JsChainError> {
+        self.check_index(index)?;
+        self.check_witness(index, &witness.0)?;
+        self.witnesses[index] = Some(witness.clone());
+        Ok(())
+    }
+
+    pub fn has_witness(&self, index: usize) -> bool {
+        self.witnesses.get(index).map_or(false, Option::is_some)
+    }
+
+    /// True once every input has its witness
+    pub fn is_complete(&self) -> bool {
+        self.witnesses.iter().all(Option::is_some)
+    }
+
+    /// The data signed by the payload authentication, available once every input has its witness
+    pub fn get_auth_data(&self) -> Result<TransactionBindingAuthData, JsChainError> {
+        Ok(self.complete_builder()?.get_auth_data())
+    }
+
+    /// Build the transaction, fails if an input has no witness
+    pub fn finalize(&self, auth: &PayloadAuthData) -> Result<Transaction, JsChainError> {
+        self.complete_builder()?.set_payload_auth(auth)
+    }
+
+    pub fn as_bytes(&self) -> Result<Vec<u8>, JsChainError> {
+        let mut writer = Writer(vec![ENCODING_VERSION]);
+        match &self.payload {
+            None => writer.u8(0),
+            Some(certificate) => {
+                writer.u8(certificate_tag(certificate));
+                writer.bytes(&Certificate(certificate.clone()).as_bytes());
+            }
+        }
+        writer.0.extend_from_slice(self.id().as_bytes().as_slice());
+
+        writer.u8(self.inputs.len() as u8);
+        for ((input, requirement), witness) in self
+            .inputs
+            .iter()
+            .zip(&self.requirements)
+            .zip(&self.witnesses)
+        {
+            writer.bytes(&input.as_bytes());
+            match requirement {
+                Requirement::Utxo(None) => writer.u8(0),
+                Requirement::Utxo(Some(address)) => {
+                    writer.u8(1);
+                    writer.bytes(&Address(address.clone()).as_bytes());
+                }
+                Requirement::LegacyUtxo(None) => writer.u8(2),
+                Requirement::LegacyUtxo(Some(address)) => {
+                    writer.u8(3);
+                    writer.bytes(format!("{}", address).as_bytes());
+                }
+                Requirement::Account => writer.u8(4),
+            }
+            match witness {
+                None => writer.u8(0),
+                Some(witness) => {
+                    writer.u8(1);
+                    writer.bytes(&witness.0.serialize_as_vec().map_err(|e| {
+                        JsChainError::with_source(
+                            ErrorKind::Serialization,
+                            "Failed to serialize the witness",
+                            e,
+                        )
+                    })?);
+                }
+            }
+        }
+
+        writer.u8(self.outputs.len() as u8);
+        for output in &self.outputs {
+            writer.bytes(&output.address().as_bytes());
+            writer.0.extend_from_slice(&output.0.value.0.to_be_bytes());
+        }
+        Ok(writer.0)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<UnsignedTransaction, JsChainError> {
+        let read_error = |e: ReadError| {
+            JsChainError::with_source(
+                ErrorKind::Deserialization,
+                "Invalid unsigned transaction",
+                e,
+            )
+        };
+        let mut buf = ReadBuf::from(bytes);
+
+        let version = buf.get_u8().map_err(read_error)?;
+        if version != ENCODING_VERSION {
+            return Err(JsChainError::new(
+                ErrorKind::Deserialization,
+                format!("Unsupported unsigned transaction version {}", version),
+            ));
+        }
+        let payload = match buf.get_u8().map_err(read_error)? {
+            0 => None,
+            tag => Some(certificate_from_bytes(tag, read_bytes(&mut buf)?)?),
+        };
+        let id = buf.get_slice(32).map_err(read_error)?.to_vec();
+
+        let input_count = buf.get_u8().map_err(read_error)?;
+        let mut transaction = UnsignedTransaction {
+            payload,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            requirements: Vec::new(),
+            witnesses: Vec::new(),
+        };
+        let mut witnesses = Vec::new();
+        for _ in 0..input_count {
+            transaction
+                .inputs
+                .push(Input::from_bytes(read_bytes(&mut buf)?)?);
+            let requirement = match buf.get_u8().map_err(read_error)? {
+                0 => Requirement::Utxo(None),
+                1 => Requirement::Utxo(Some(Address::from_bytes(read_bytes(&mut buf)?)?.0)),
+                2 => Requirement::LegacyUtxo(None),
+                3 => {
+                    let address = String::from_utf8_lossy(read_bytes(&mut buf)?);
+                    Requirement::LegacyUtxo(Some(address.parse().map_err(|e| {
+                        JsChainError::with_source(
+                            ErrorKind::InvalidAddress,
+                            "Invalid legacy address",
+                            e,
+                        )
+                    })?))
+                }
+                4 => Requirement::Account,
+                tag => {
+                    return Err(JsChainError::new(
+                        ErrorKind::Deserialization,
+                        format!("Unknown signing requirement {}", tag),
+                    ))
+                }
+            };
+            transaction.requirements.push(requirement);
+            witnesses.push(match buf.get_u8().map_err(read_error)? {
+                0 => None,
+                _ => Some(
+                    tx::Witness::read(&mut ReadBuf::from(read_bytes(&mut buf)?))
+                        .map_err(read_error)?,
+                ),
+            });
+        }
+
+        let output_count = buf.get_u8().map_err(read_error)?;
+        for _ in 0..output_count {
+            let address = Address::from_bytes(read_bytes(&mut buf)?)?;
+            let value = Value::from(buf.get_u64().map_err(read_error)?);
+            transaction.outputs.push(Output::new(&address, &value));
+        }
+        buf.expect_end().map_err(read_error)?;
+
+        if transaction.id().as_bytes() != id {
+            return Err(JsChainError::new(
+                ErrorKind::Deserialization,
+                "The transaction sign data hash doesn't match the transaction",
+            ));
+        }
+        transaction.witnesses = vec![None; transaction.inputs.len()];
+        for (index, witness) in witnesses.into_iter().enumerate() {
+            if let Some(witness) = witness {
+                transaction.add_witness(index, &Witness(witness))?;
+            }
+        }
+        Ok(transaction)
+    }
+
+    pub fn to_bech32(&self) -> Result<String, JsChainError> {
+        Bech32::new(BECH32_PREFIX.to_string(), self.as_bytes()?.to_base32())
+            .map(|bech32| bech32.to_string())
+            .map_err(|e| {
+                JsChainError::with_source(
+                    ErrorKind::Bech32,
+                    "Failed to encode the unsigned transaction",
+                    e,
+                )
+            })
+    }
+
+    pub fn from_bech32(bech32_str: &str) -> Result<UnsignedTransaction, JsChainError> {
+        let bech32 = bech32_str.parse::<Bech32>().map_err(|e| {
+            JsChainError::with_source(ErrorKind::Bech32, "Invalid unsigned transaction", e)
+        })?;
+        if bech32.hrp() != BECH32_PREFIX {
+            return Err(JsChainError::new(
+                ErrorKind::Bech32,
+                format!(
+                    "Expected the {} prefix, found {}",
+                    BECH32_PREFIX,
+                    bech32.hrp()
+                ),
+            ));
+        }
+        let bytes = Vec::<u8>::from_base32(bech32.data()).map_err(|e| {
+            JsChainError::with_source(ErrorKind::Bech32, "Invalid unsigned transaction", e)
+        })?;
+        UnsignedTransaction::from_bytes(&bytes)
+    }
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, byte: u8) {
+        self.0.push(byte)
+    }
+
+    /// length prefixed bytes
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0
+            .extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+fn read_bytes<'a>(buf: &mut ReadBuf<'a>) -> Result<&'a [u8], JsChainError> {
+    buf.get_u32()
+        .and_then(|length| buf.get_slice(length as usize))
+        .map_err(|e| {
+            JsChainError::with_source(
+                ErrorKind::Deserialization,
+                "Invalid unsigned transaction",
+                e,
+            )
+        })
+}
+
+fn certificate_tag(certificate: &certificate::Certificate) -> u8 {
+    match certificate {
+        certificate::Certificate::StakeDelegation(_) => 1,
+        certificate::Certificate::OwnerStakeDelegation(_) => 2,
+        certificate::Certificate::PoolRegistration(_) => 3,
+        certificate::Certificate::PoolRetirement(_) => 4,
+        certificate::Certificate::PoolUpdate(_) => 5,
+    }
+}
+
+fn certificate_from_bytes(tag: u8, bytes: &[u8]) -> Result<certificate::Certificate, JsChainError> {
+    use certificate::Certificate as C;
+    Ok(match tag {
+        1 => C::StakeDelegation(StakeDelegation::from_bytes(bytes)?.0),
+        2 => C::OwnerStakeDelegation(OwnerStakeDelegation::from_bytes(bytes)?.0),
+        3 => C::PoolRegistration(PoolRegistration::from_bytes(bytes)?.0),
+        4 => C::PoolRetirement(PoolRetirement::from_bytes(bytes)?.0),
+        5 => C::PoolUpdate(PoolUpdate::from_bytes(bytes)?.0),
+        _ => {
+            return Err(JsChainError::new(
+                ErrorKind::Deserialization,
+                format!("Unknown certificate type {}", tag),
+            ))
+        }
+    })
+}
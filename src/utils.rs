@@ -12,10 +12,10 @@ pub fn set_panic_hook() {
 #[macro_export]
 macro_rules! impl_collection {
     ($collection:ident, $type:ty) => {
-        #[wasm_bindgen]
+        #[cfg_attr(feature = "wasm", wasm_bindgen)]
         pub struct $collection(Vec<$type>);
 
-        #[wasm_bindgen]
+        #[cfg_attr(feature = "wasm", wasm_bindgen)]
         impl $collection {
             pub fn new() -> $collection {
                 Self(vec![])
@@ -0,0 +1,301 @@
+//! Tracking of the unspent outputs of a set of addresses
+//!
+//! The store is fed with the blocks of the chain in order, and keeps enough
+//! information for each applied block to undo it when switching to a fork.
+
+use crate::{
+    tx, value, Address, Block, BlockId, ErrorKind, JsChainError, UtxoPointer, UtxoPointers, Value,
+};
+use chain::fragment::{Fragment, FragmentId};
+use chain::key;
+use chain_core::property::Block as _;
+use chain_core::property::Fragment as _;
+use chain_impl_mockchain as chain;
+use std::collections::{HashMap, HashSet, VecDeque};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+type UtxoKey = (FragmentId, u8);
+
+/// number of blocks kept to be reverted, once a block is deeper than this
+/// it is considered stable
+const DEFAULT_MAX_ROLLBACK_DEPTH: u32 = 100;
+
+#[derive(Clone, PartialEq, Eq)]
+enum Owner {
+    Address(chain_addr::Address),
+    /// legacy (byron) addresses are only found in old utxo declarations, they are
+    /// identified by their base58 representation
+    Legacy(String),
+}
+
+#[derive(Clone)]
+struct Utxo {
+    owner: Owner,
+    value: value::Value,
+}
+
+/// Changes made to the store by a block, in order to be able to revert them
+struct AppliedBlock {
+    id: key::Hash,
+    added: Vec<UtxoKey>,
+    spent: Vec<(UtxoKey, Utxo)>,
+}
+
+/// Set of unspent outputs belonging to the watched addresses
+///
+/// ```javascript
+/// const store = new UtxoStore();
+/// store.watch_address(address);
+/// blocks.forEach(block => store.apply_block(block));
+/// const utxos = store.utxos(address);
+/// // a fork was detected, go back to the common ancestor
+/// store.rollback_to(ancestor_id);
+/// ```
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct UtxoStore {
+    watched: HashSet<chain_addr::Address>,
+    watched_legacy: HashSet<String>,
+    unspent: HashMap<UtxoKey, Utxo>,
+    history: VecDeque<AppliedBlock>,
+    max_rollback_depth: u32,
+}
+
+impl Default for UtxoStore {
+    fn default() -> Self {
+        UtxoStore {
+            watched: HashSet::new(),
+            watched_legacy: HashSet::new(),
+            unspent: HashMap::new(),
+            history: VecDeque::new(),
+            max_rollback_depth: DEFAULT_MAX_ROLLBACK_DEPTH,
+        }
+    }
+}
+
+impl UtxoStore {
+    fn is_watched(&self, owner: &Owner) -> bool {
+        match owner {
+            Owner::Address(address) => self.watched.contains(address),
+            Owner::Legacy(address) => self.watched_legacy.contains(address),
+        }
+    }
+
+    fn apply_fragment(&mut self, fragment: &Fragment, applied: &mut AppliedBlock) {
+        let fragment_id = fragment.id();
+
+        let (inputs, outputs): (Vec<tx::Input>, Vec<(Owner, value::Value)>) = match fragment {
+            Fragment::OldUtxoDeclaration(declaration) => (
+                Vec::new(),
+                declaration
+                    .addrs
+                    .iter()
+                    .map(|(address, value)| (Owner::Legacy(format!("{}", address)), *value))
+                    .collect(),
+            ),
+            _ => match crate::Fragment::from(fragment.clone()).get_transaction() {
+                Ok(transaction) => (
+                    transaction.0.inputs(),
+                    transaction
+                        .0
+                        .outputs()
+                        .into_iter()
+                        .map(|output| (Owner::Address(output.address), output.value))
+                        .collect(),
+                ),
+                Err(_) => return,
+            },
+        };
+
+        for input in inputs {
+            if let tx::InputEnum::UtxoInput(pointer) = input.to_enum() {
+                let key = (pointer.transaction_id, pointer.output_index);
+                if let Some(utxo) = self.unspent.remove(&key) {
+                    applied.spent.push((key, utxo));
+                }
+            }
+        }
+
+        for (index, (owner, value)) in outputs.into_iter().enumerate() {
+            if self.is_watched(&owner) {
+                let key = (fragment_id, index as u8);
+                self.unspent.insert(key, Utxo { owner, value });
+                applied.added.push(key);
+            }
+        }
+    }
+
+    /// forget the changes of the blocks deeper than the maximum rollback depth,
+    /// the tip is always kept
+    fn prune(&mut self) {
+        while self.history.len() > self.max_rollback_depth as usize + 1 {
+            self.history.pop_front();
+        }
+    }
+
+    fn revert(&mut self, applied: AppliedBlock) {
+        for key in applied.added {
+            self.unspent.remove(&key);
+        }
+        for (key, utxo) in applied.spent {
+            self.unspent.insert(key, utxo);
+        }
+    }
+
+    fn pointers<F>(&self, filter: F) -> UtxoPointers
+    where
+        F: Fn(&Owner) -> bool,
+    {
+        self.unspent
+            .iter()
+            .filter(|(_, utxo)| filter(&utxo.owner))
+            .map(|((transaction_id, output_index), utxo)| {
+                UtxoPointer(tx::UtxoPointer {
+                    transaction_id: *transaction_id,
+                    output_index: *output_index,
+                    value: utxo.value,
+                })
+            })
+            .collect::<Vec<UtxoPointer>>()
+            .into()
+    }
+
+    fn balance_of<F>(&self, filter: F) -> Value
+    where
+        F: Fn(&Owner) -> bool,
+    {
+        // the values come from blocks that are not validated
+        self.unspent
+            .values()
+            .filter(|utxo| filter(&utxo.owner))
+            .fold(0u64, |total, utxo| {
+                total.saturating_add(*utxo.value.as_ref())
+            })
+            .into()
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl UtxoStore {
+    #[cfg_attr(feature = "wasm", wasm_bindgen(constructor))]
+    pub fn new() -> UtxoStore {
+        UtxoStore::default()
+    }
+
+    /// Number of blocks that can be reverted with `rollback_to`, 100 by default.
+    /// The changes of the older blocks are forgotten to bound the memory used.
+    pub fn set_max_rollback_depth(&mut self, depth: u32) {
+        self.max_rollback_depth = depth;
+        self.prune();
+    }
+
+    pub fn max_rollback_depth(&self) -> u32 {
+        self.max_rollback_depth
+    }
+
+    /// Track the outputs sent to the given address. Only the blocks applied
+    /// after this call are taken into account.
+    pub fn watch_address(&mut self, address: &Address) {
+        self.watched.insert(address.0.clone());
+    }
+
+    /// Track the outputs of old utxo declarations for the given legacy (base58) address
+    pub fn watch_legacy_address(&mut self, address: &str) {
+        self.watched_legacy.insert(address.to_owned());
+    }
+
+    /// Apply the fragments of the block: add the outputs for the watched addresses
+    /// and remove the ones spent by the inputs.
+    ///
+    /// The block must be a child of the last applied block.
+    pub fn apply_block(&mut self, block: &Block) -> Result<(), JsChainError> {
+        if let Some(tip) = self.history.back() {
+            if block.0.parent_id() != tip.id {
+                return Err(JsChainError::new(
+                    ErrorKind::InvalidBlock,
+                    "Block is not a child of the current tip, rollback to its parent first",
+                ));
+            }
+        }
+
+        let mut applied = AppliedBlock {
+            id: block.0.id(),
+            added: Vec::new(),
+            spent: Vec::new(),
+        };
+        for fragment in block.0.fragments() {
+            self.apply_fragment(fragment, &mut applied);
+        }
+        self.history.push_back(applied);
+        self.prune();
+        Ok(())
+    }
+
+    /// Undo the blocks applied after the given one, which becomes the new tip.
+    /// The block must be at most `max_rollback_depth` blocks below the tip.
+    pub fn rollback_to(&mut self, block_id: &BlockId) -> Result<(), JsChainError> {
+        let position = self
+            .history
+            .iter()
+            .rposition(|applied| applied.id == block_id.0)
+            .ok_or_else(|| {
+                JsChainError::new(
+                    ErrorKind::InvalidBlock,
+                    "Block was not applied to the store, or is deeper than the maximum rollback depth",
+                )
+            })?;
+
+        while self.history.len() > position + 1 {
+            let applied = self.history.pop_back().expect("history is not empty");
+            self.revert(applied);
+        }
+        Ok(())
+    }
+
+    /// Id of the last applied block, if any
+    pub fn tip(&self) -> Option<BlockId> {
+        self.history
+            .back()
+            .map(|applied| BlockId(applied.id.clone()))
+    }
+
+    /// Unspent outputs sent to the given address
+    pub fn utxos(&self, address: &Address) -> UtxoPointers {
+        self.pointers(|owner| match owner {
+            Owner::Address(owned) => *owned == address.0,
+            Owner::Legacy(_) => false,
+        })
+    }
+
+    /// Unspent outputs declared for the given legacy address
+    pub fn legacy_utxos(&self, address: &str) -> UtxoPointers {
+        self.pointers(|owner| match owner {
+            Owner::Legacy(owned) => owned == address,
+            Owner::Address(_) => false,
+        })
+    }
+
+    /// Unspent outputs of all the watched addresses
+    pub fn all_utxos(&self) -> UtxoPointers {
+        self.pointers(|_| true)
+    }
+
+    pub fn balance(&self, address: &Address) -> Value {
+        self.balance_of(|owner| match owner {
+            Owner::Address(owned) => *owned == address.0,
+            Owner::Legacy(_) => false,
+        })
+    }
+
+    pub fn legacy_balance(&self, address: &str) -> Value {
+        self.balance_of(|owner| match owner {
+            Owner::Legacy(owned) => owned == address,
+            Owner::Address(_) => false,
+        })
+    }
+
+    /// Sum of the unspent outputs of all the watched addresses
+    pub fn total_balance(&self) -> Value {
+        self.balance_of(|_| true)
+    }
+}
@@ -0,0 +1,285 @@
+//! Verification of the witnesses
+//!
+//! A witness only holds a signature, checking it needs what the input spends:
+//! the address of the spent output for an utxo input, the spending counter
+//! of the account for an account input, and the declaration of the account
+//! for a multisig account.
+
+use crate::{
+    account, key, tx, Address, ErrorKind, Hash, JsChainError, MultisigDeclaration, PublicKey,
+    SpendingCounter, Transaction, TransactionSignDataHash, Witness,
+};
+use chain::legacy;
+use chain_crypto as crypto;
+use chain_impl_mockchain as chain;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Result of the verification of the witness of an input
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WitnessStatus {
+    Valid,
+    InvalidSignature,
+    /// the witness is not of the kind the input needs (utxo, account, legacy or multisig)
+    WrongKind,
+    /// the key of the witness or the multisig declaration is not the one of the input
+    WrongKey,
+    /// the transaction has no witness for the input
+    Missing,
+}
+
+#[derive(Clone)]
+enum Resolution {
+    Utxo(crypto::PublicKey<crypto::Ed25519>),
+    LegacyUtxo(legacy::OldAddress),
+    Account(crypto::PublicKey<crypto::Ed25519>, account::SpendingCounter),
+    Multisig(MultisigDeclaration, account::SpendingCounter),
+}
+
+/// What an input spends, needed to verify its witness
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone)]
+pub struct ResolvedInput(Resolution);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl ResolvedInput {
+    /// Utxo input spending an output of the given single or group address
+    pub fn utxo(address: &Address) -> Result<ResolvedInput, JsChainError> {
+        match address.0.kind() {
+            chain_addr::Kind::Single(key) | chain_addr::Kind::Group(key, _) => {
+                Ok(ResolvedInput(Resolution::Utxo(key.clone())))
+            }
+            _ => Err(JsChainError::new(
+                ErrorKind::WrongType,
+                "Utxo inputs spend single or group addresses",
+            )),
+        }
+    }
+
+    /// Utxo input spending an output of the given legacy (byron) base58 address
+    pub fn legacy_utxo(address: &str) -> Result<ResolvedInput, JsChainError> {
+        address
+            .parse::<legacy::OldAddress>()
+            .map_err(|e| {
+                JsChainError::with_source(ErrorKind::InvalidAddress, "Invalid legacy address", e)
+            })
+            .map(|address| ResolvedInput(Resolution::LegacyUtxo(address)))
+    }
+
+    /// Input of a single account, the key is the one of the account
+    pub fn account(key: &PublicKey, spending_counter: &SpendingCounter) -> ResolvedInput {
+        ResolvedInput(Resolution::Account(key.0.clone(), spending_counter.0))
+    }
+
+    pub fn multisig_account(
+        declaration: &MultisigDeclaration,
+        spending_counter: &SpendingCounter,
+    ) -> ResolvedInput {
+        ResolvedInput(Resolution::Multisig(
+            declaration.clone(),
+            spending_counter.0,
+        ))
+    }
+}
+
+crate::impl_collection!(ResolvedInputs, ResolvedInput);
+
+fn verify_witness(
+    witness: &tx::Witness,
+    genesis_hash: &key::Hash,
+    transaction_id: &tx::TransactionSignDataHash,
+    resolution: &Resolution,
+) -> Result<WitnessStatus, JsChainError> {
+    let status = |verification| match verification {
+        crypto::Verification::Success => WitnessStatus::Valid,
+        crypto::Verification::Failed => WitnessStatus::InvalidSignature,
+    };
+    Ok(match (witness, resolution) {
+        (tx::Witness::Utxo(signature), Resolution::Utxo(key)) => {
+            let data = tx::WitnessUtxoData::new(genesis_hash, transaction_id, false);
+            status(signature.verify(key, &data))
+        }
+        (tx::Witness::OldUtxo(key, signature), Resolution::LegacyUtxo(address)) => {
+            if !address.identify_with(key) {
+                return Ok(WitnessStatus::WrongKey);
+            }
+            let data = tx::WitnessUtxoData::new(genesis_hash, transaction_id, true);
+            status(signature.verify(key, &data))
+        }
+        (tx::Witness::Account(signature), Resolution::Account(key, spending_counter)) => {
+            let data = tx::WitnessAccountData::new(genesis_hash, transaction_id, spending_counter);
+            status(signature.verify(key, &data))
+        }
+        (tx::Witness::Multisig(witness), Resolution::Multisig(declaration, spending_counter)) => {
+            declaration.identifier()?;
+            let data = tx::WitnessMultisigData::new(genesis_hash, transaction_id, spending_counter);
+            if witness.verify(&declaration.to_declaration(), &data) {
+                WitnessStatus::Valid
+            } else {
+                WitnessStatus::InvalidSignature
+            }
+        }
+        _ => WitnessStatus::WrongKind,
+    })
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Witness {
+    /// Check the witness of an utxo or account input against the address it
+    /// spends from, the spending counter is only used for the accounts
+    ///
+    /// ```javascript
+    /// const witness = Witness.from_external_utxo(UtxoWitness.from_bytes(signature));
+    /// if (!witness.verify(genesis_hash, transaction.id(), address, SpendingCounter.zero())) {
+    ///     throw new Error(&#39;invalid hardware wallet signature&#39;);
+    /// }
+    /// ```
+    pub fn verify(
+        &self,
+        genesis_hash: &Hash,
+        transaction_id: &TransactionSignDataHash,
+        address: &Address,
+        spending_counter: &SpendingCounter,
+    ) -> Result<bool, JsChainError> {
+        let resolution = match address.0.kind() {
+            chain_addr::Kind::Account(key) => Resolution::Account(key.clone(), spending_counter.0),
+            chain_addr::Kind::Multisig(_) => {
+                return Err(JsChainError::new(
+                    ErrorKind::WrongType,
+                    "Multisig witnesses are verified against the declaration of the account",
+                ))
+            }
+            _ => ResolvedInput::utxo(address)?.0,
+        };
+        let status = verify_witness(&self.0, &genesis_hash.0, &transaction_id.0, &resolution)?;
+        Ok(status == WitnessStatus::Valid)
+    }
+
+    /// Same as `verify`, with the public key signing the input instead of the address
+    pub fn verify_with_key(
+        &self,
+        genesis_hash: &Hash,
+        transaction_id: &TransactionSignDataHash,
+        key: &PublicKey,
+        spending_counter: &SpendingCounter,
+    ) -> Result<bool, JsChainError> {
+        let resolution = match self.0 {
+            tx::Witness::Account(_) => Resolution::Account(key.0.clone(), spending_counter.0),
+            _ => Resolution::Utxo(key.0.clone()),
+        };
+        let status = verify_witness(&self.0, &genesis_hash.0, &transaction_id.0, &resolution)?;
+        Ok(status == WitnessStatus::Valid)
+    }
+
+    /// Check a legacy utxo witness against the base58 address it spends from
+    pub fn verify_legacy(
+        &self,
+        genesis_hash: &Hash,
+        transaction_id: &TransactionSignDataHash,
+        address: &str,
+    ) -> Result<bool, JsChainError> {
+        let resolution = ResolvedInput::legacy_utxo(address)?.0;
+        let status = verify_witness(&self.0, &genesis_hash.0, &transaction_id.0, &resolution)?;
+        Ok(status == WitnessStatus::Valid)
+    }
+
+    pub fn verify_multisig(
+        &self,
+        genesis_hash: &Hash,
+        transaction_id: &TransactionSignDataHash,
+        declaration: &MultisigDeclaration,
+        spending_counter: &SpendingCounter,
+    ) -> Result<bool, JsChainError> {
+        let resolution = Resolution::Multisig(declaration.clone(), spending_counter.0);
+        let status = verify_witness(&self.0, &genesis_hash.0, &transaction_id.0, &resolution)?;
+        Ok(status == WitnessStatus::Valid)
+    }
+}
+
+/// Status of the witness of each input of a transaction
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct WitnessVerification(Vec<WitnessStatus>);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl WitnessVerification {
+    pub fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn get(&self, index: usize) -> WitnessStatus {
+        self.0[index]
+    }
+
+    /// True if the witnesses of all the inputs are valid
+    pub fn is_valid(&self) -> bool {
+        self.0.iter().all(|status| *status == WitnessStatus::Valid)
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Transaction {
+    /// Verify the witness of every input, the resolved inputs are in the order
+    /// of the inputs of the transaction and must be of the same kind (utxo or
+    /// account) as the inputs
+    pub fn verify_witnesses(
+        &self,
+        genesis_hash: &Hash,
+        resolved_inputs: &ResolvedInputs,
+    ) -> Result<WitnessVerification, JsChainError> {
+        let inputs = self.0.inputs();
+        let witnesses = self.witnesses();
+        if witnesses.0.len() > inputs.len() {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The transaction has {} witnesses for {} inputs",
+                    witnesses.0.len(),
+                    inputs.len()
+                ),
+            ));
+        }
+        if resolved_inputs.0.len() != inputs.len() {
+            return Err(JsChainError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Expected {} resolved inputs, found {}",
+                    inputs.len(),
+                    resolved_inputs.0.len()
+                ),
+            ));
+        }
+        let transaction_id = self.id();
+        let mut statuses = Vec::with_capacity(inputs.len());
+        for (index, (input, resolved)) in inputs.iter().zip(resolved_inputs.0.iter()).enumerate() {
+            let key_matches = match (input.to_enum(), &resolved.0) {
+                (tx::InputEnum::UtxoInput(_), Resolution::Utxo(_))
+                | (tx::InputEnum::UtxoInput(_), Resolution::LegacyUtxo(_)) => true,
+                (tx::InputEnum::AccountInput(id, _), Resolution::Account(key, _)) => id
+                    .to_single_account()
+                    .map_or(false, |id| id == key.clone().into()),
+                (tx::InputEnum::AccountInput(id, _), Resolution::Multisig(declaration, _)) => {
+                    declaration.identifier()? == id.to_multi_account()
+                }
+                _ => {
+                    return Err(JsChainError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Resolved input {} is not of the kind of the input (utxo or account)",
+                            index
+                        ),
+                    ))
+                }
+            };
+            let status = match witnesses.0.get(index) {
+                None => WitnessStatus::Missing,
+                Some(_) if !key_matches => WitnessStatus::WrongKey,
+                Some(witness) => {
+                    verify_witness(&witness.0, &genesis_hash.0, &transaction_id.0, &resolved.0)?
+                }
+            };
+            statuses.push(status);
+        }
+        Ok(WitnessVerification(statuses))
+    }
+}
@@ -0,0 +1,32 @@
+const rust = import('../pkg/js_chain_libs');
+
+// Genesis block of the tests, like `mock_block0_builder` of the native tests:
+// BFT with a single leader, generated if not given
+export async function mockBlock0Builder(leader) {
+  const {
+    AddressDiscrimination,
+    Block0Builder,
+    ConsensusVersion,
+    PrivateKey
+  } = await rust;
+
+  const builder = Block0Builder.new(
+    AddressDiscrimination.Test,
+    ConsensusVersion.Bft,
+    1576000000,
+    2,
+    100
+  );
+  builder.add_bft_leader(leader || PrivateKey.generate_ed25519().to_public());
+  return builder;
+}
+
+// The error thrown by the function, fails if it doesn't throw
+export function thrownBy(f) {
+  try {
+    f();
+  } catch (e) {
+    return e;
+  }
+  throw new Error('expected the function to throw');
+}
@@ -1,4 +1,26 @@
 // eslint-disable-next-line no-unused-vars
 import test_make_transaction from './test_make_transaction';
 import test_get_block_messages from './test_get_block_messages';
-import test_stake_pool_registration_certificate from './test_stake_pool_registration_certificate';
\ No newline at end of file
+import test_stake_pool_registration_certificate from './test_stake_pool_registration_certificate';
+import test_mnemonic from './test_mnemonic';
+import test_derivation_path from './test_derivation_path';
+import test_account_discovery from './test_account_discovery';
+import test_utxo_store from './test_utxo_store';
+import test_coin_selection from './test_coin_selection';
+import test_ledger_simulator from './test_ledger_simulator';
+import test_network_settings from './test_network_settings';
+import test_block0_builder from './test_block0_builder';
+import test_time_converter from './test_time_converter';
+import test_errors from './test_errors';
+import test_json from './test_json';
+import test_multisig from './test_multisig';
+import test_unsigned_transaction from './test_unsigned_transaction';
+import test_witness_verification from './test_witness_verification';
+import test_signer from './test_signer';
+import test_stake_pool_keys from './test_stake_pool_keys';
+import test_leader_schedule from './test_leader_schedule';
+import test_header from './test_header';
+import test_header_chain from './test_header_chain';
+import test_contents_proof from './test_contents_proof';
+import test_block_stream_decoder from './test_block_stream_decoder';
+import test_history from './test_history';
\ No newline at end of file
@@ -0,0 +1,894 @@
+//! Test suite of the rust API, without the javascript bindings.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use chain_core::property::{Deserialize as _, Serialize as _};
+use chain_crypto as crypto;
+use chain_impl_mockchain as chain;
+use js_chain_libs::*;
+use std::str::FromStr as _;
+
+fn mock_io_builder(input: u64, output: u64) -> InputOutputBuilder {
+    let mut builder = InputOutputBuilder::empty();
+
+    let txid = FragmentId::calculate(&[0]);
+    let utxopointer = UtxoPointer::new(&txid, 0, &input.into());
+    let input = Input::from_utxo(&utxopointer);
+
+    builder.add_input(&input).unwrap();
+
+    let output_address =
+        Address::from_string("ca1qh9u0nxmnfg7af8ycuygx57p5xgzmnmgtaeer9xun7hly6mlgt3pj2xk344")
+            .unwrap();
+    builder.add_output(&output_address, &output.into()).unwrap();
+    builder
+}
+
+/// BFT genesis block with the given leader, slots of 2 seconds and epochs of 100 slots
+fn mock_block0_builder(leader: &PublicKey) -> Block0Builder {
+    let mut builder = Block0Builder::new(
+        AddressDiscrimination::Test,
+        ConsensusVersion::Bft,
+        1576000000.0,
+        2,
+        100,
+    );
+    builder.add_bft_leader(leader);
+    builder
+}
+
+/// BFT block with the given parent, chain length and date, signed by the
+/// leader (an ed25519 key)
+fn mock_bft_block(
+    parent: &Block,
+    chain_length: u32,
+    date: BlockDate,
+    leader: &PrivateKey,
+    fragments: &[&Fragment],
+) -> Block {
+    let mut contents = chain::block::ContentsBuilder::new();
+    for fragment in fragments {
+        let bytes = fragment.as_bytes().unwrap();
+        contents.push(chain::fragment::Fragment::deserialize(bytes.as_slice()).unwrap());
+    }
+    let contents = contents.into();
+    let parent_id = chain::block::HeaderId::deserialize(parent.id().as_bytes().as_slice()).unwrap();
+    let secret = crypto::SecretKey::<crypto::Ed25519>::from_binary(&leader.as_bytes()).unwrap();
+    let header =
+        chain::block::HeaderBuilderNew::new(chain::block::BlockVersion::Ed25519Signed, &contents)
+            .set_parent(&parent_id, chain::block::ChainLength::from(chain_length))
+            .set_date(chain::block::BlockDate {
+                epoch: date.epoch(),
+                slot_id: date.slot(),
+            })
+            .into_bft_builder()
+            .unwrap()
+            .sign_using(&secret)
+            .generalize();
+    chain::block::Block { header, contents }.into()
+}
+
+#[test]
+fn transaction_builder_balance() {
+    let iobuilder = mock_io_builder(32, 20);
+    let fee_algorithm = Fee::linear_fee(&2u64.into(), &0u64.into(), &0u64.into());
+    let balance = iobuilder
+        .get_balance(&Payload::no_payload(), &fee_algorithm)
+        .unwrap();
+
+    assert_eq!(balance.get_sign(), "positive");
+    assert_eq!(balance.get_value().to_str(), "10");
+}
+
+#[test]
+fn transaction_builder_finalize_good_case() {
+    let iobuilder = mock_io_builder(32, 20);
+    let fee_algorithm = Fee::linear_fee(&2u64.into(), &0u64.into(), &0u64.into());
+
+    let ios = iobuilder
+        .seal_with_output_policy(
+            &Payload::no_payload(),
+            &fee_algorithm,
+            &OutputPolicy::forget(),
+        )
+        .unwrap();
+
+    let set_witness = TransactionBuilder::new()
+        .no_payload()
+        .set_ios(&ios.inputs(), &ios.outputs());
+
+    let genesis_hash = Hash::calculate(&[0]);
+    let txid = set_witness.get_auth_data_for_witness();
+    let key = PrivateKey::from_bech32("ed25519e_sk1lzkckzvwh7gn5f0krrmrxlpsywypu3kka2u82l3akm5gr8khra8suz6zv5jcwg8h6jy4pjs4dfvcrja07q9758xctp6cgkn5ykkgj9cts0mef").unwrap();
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(Witness::for_utxo(&genesis_hash, &txid, &key));
+
+    let transaction = set_witness
+        .set_witnesses(&witnesses)
+        .set_payload_auth(&PayloadAuthData::for_no_payload());
+
+    assert!(transaction.is_ok())
+}
+
+#[test]
+fn io_builder_finalize_not_enough_input() {
+    let iobuilder = mock_io_builder(30, 31);
+    let fee_algorithm = Fee::linear_fee(&2u64.into(), &0u64.into(), &0u64.into());
+
+    let error = iobuilder
+        .seal_with_output_policy(
+            &Payload::no_payload(),
+            &fee_algorithm,
+            &OutputPolicy::forget(),
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::FeeAlgorithm);
+}
+
+#[test]
+fn invalid_address_error() {
+    let error = Address::from_string("not an address").err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidAddress);
+}
+
+#[test]
+fn account_address_from_public_key() {
+    let public_key = PublicKey::from_bech32(
+        "ed25519_pk1kj8yvfrh5tg7n62kdcw3kw6zvtcafgckz4z9s6vc608pzt7exzys4s9gs8",
+    )
+    .unwrap();
+    let address = Address::account_from_public_key(&public_key, AddressDiscrimination::Test);
+    assert_eq!(
+        address.to_string("ta"),
+        "ta1sk6gu33yw73dr60f2ehp6xemgf30r49rzc25gkrfnrfuuyf0mycgjm9vc4c"
+    );
+}
+
+#[test]
+fn account_identifier_from_address() {
+    let address =
+        Address::from_string("ca1skmmqxvaesaew6aygcpkshrs0et80rh6hutkzlt6k6dpgewyarwv7zl5efq")
+            .unwrap();
+
+    let account = Account::from_address(&address).unwrap();
+    let expected = "b7b0199dcc3b976ba44603685c707e56778efabf17617d7ab69a1465c4e8dccf";
+    assert_eq!(account.to_identifier().to_hex(), expected);
+}
+
+#[test]
+fn transaction_json_round_trip() {
+    let input = Input::from_utxo(&UtxoPointer::new(
+        &FragmentId::calculate(&[0]),
+        1,
+        &32u64.into(),
+    ));
+    let mut inputs = Inputs::new();
+    inputs.add(&input);
+    let mut outputs = Outputs::new();
+    outputs.add(&Output::new(
+        &Address::from_string("ca1qh9u0nxmnfg7af8ycuygx57p5xgzmnmgtaeer9xun7hly6mlgt3pj2xk344")
+            .unwrap(),
+        &20u64.into(),
+    ));
+    let transaction = TransactionBuilder::new()
+        .no_payload()
+        .set_ios(&inputs, &outputs)
+        .set_witnesses(&Witnesses::new())
+        .set_payload_auth(&PayloadAuthData::for_no_payload())
+        .unwrap();
+    let fragment = Fragment::from_transaction(&transaction);
+
+    let json = fragment.to_json().unwrap();
+    assert!(json.contains(r#""type":"transaction""#));
+    assert!(json.contains(r#""value":"32""#));
+
+    let imported = Fragment::from_json(&json).unwrap();
+    assert_eq!(imported.as_bytes().unwrap(), fragment.as_bytes().unwrap());
+}
+
+#[test]
+fn invalid_json_error() {
+    let error = Output::from_json(r#"{"address": "ca1q", "value": "1"}"#)
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidAddress);
+
+    let error = Address::from_json("{").err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::Deserialization);
+}
+
+#[test]
+fn multisig_declaration() {
+    let keys: Vec<PublicKey> = (0..3)
+        .map(|_| PrivateKey::generate_ed25519().unwrap().to_public())
+        .collect();
+    let mut declaration = MultisigDeclaration::new(2);
+    for key in &keys {
+        declaration.add_owner(key);
+    }
+
+    let address = declaration.to_address(AddressDiscrimination::Test).unwrap();
+    assert!(address.to_multisig_address().is_some());
+
+    let mut invalid = MultisigDeclaration::new(4);
+    invalid.add_sub_declaration(&declaration);
+    let error = invalid.to_account().err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+}
+
+#[test]
+fn unsigned_transaction_round_trip() {
+    let iobuilder = mock_io_builder(32, 20);
+    let ios = iobuilder.build();
+    let mut unsigned =
+        UnsignedTransaction::new(&Payload::no_payload(), &ios.inputs(), &ios.outputs()).unwrap();
+    assert_eq!(
+        unsigned.requirement(0).unwrap().kind(),
+        SigningRequirementKind::Utxo
+    );
+    assert!(unsigned
+        .finalize(&PayloadAuthData::for_no_payload())
+        .is_err());
+
+    let encoded = unsigned.to_bech32().unwrap();
+    let mut received = UnsignedTransaction::from_bech32(&encoded).unwrap();
+    assert_eq!(received.id().as_bytes(), unsigned.id().as_bytes());
+
+    let genesis_hash = Hash::calculate(&[0]);
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let witness = Witness::for_utxo(&genesis_hash, &received.id(), &key);
+    received.add_witness(0, &witness).unwrap();
+    assert!(received.is_complete());
+
+    unsigned = UnsignedTransaction::from_bytes(&received.as_bytes().unwrap()).unwrap();
+    let transaction = unsigned
+        .finalize(&PayloadAuthData::for_no_payload())
+        .unwrap();
+    assert_eq!(transaction.witnesses().size(), 1);
+}
+
+#[test]
+fn unsigned_transaction_witness_kind() {
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let mut declaration = MultisigDeclaration::new(1);
+    declaration.add_owner(&key.to_public());
+    let single = Account::single_from_public_key(&key.to_public());
+    let multi = declaration.to_account().unwrap();
+
+    let mut inputs = Inputs::new();
+    inputs.add(Input::from_account(&single, &100u64.into()));
+    inputs.add(Input::from_account(&multi, &100u64.into()));
+    let mut outputs = Outputs::new();
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    outputs.add(Output::new(&address, &190u64.into()));
+    let mut unsigned = UnsignedTransaction::new(&Payload::no_payload(), &inputs, &outputs).unwrap();
+
+    let genesis_hash = Hash::calculate(&[0]);
+    let counter = SpendingCounter::zero();
+    let account_witness = Witness::for_account(&genesis_hash, &unsigned.id(), &key, &counter);
+    let mut signatures = MultisigPartialSignatures::new();
+    signatures.add(MultisigPartialSignature::sign(
+        &genesis_hash,
+        &unsigned.id(),
+        &counter,
+        &key,
+    ));
+    let multisig_witness = Witness::for_multisig_account(&declaration, &signatures).unwrap();
+
+    // both inputs require an account witness, of the kind of their account
+    let error = unsigned.add_witness(0, &multisig_witness).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::WrongType);
+    let error = unsigned.add_witness(1, &account_witness).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::WrongType);
+    unsigned.add_witness(0, &account_witness).unwrap();
+    unsigned.add_witness(1, &multisig_witness).unwrap();
+    assert!(unsigned.is_complete());
+}
+
+#[test]
+fn witness_verification() {
+    let iobuilder = mock_io_builder(32, 20);
+    let ios = iobuilder.build();
+    let set_witness = TransactionBuilder::new()
+        .no_payload()
+        .set_ios(&ios.inputs(), &ios.outputs());
+
+    let genesis_hash = Hash::calculate(&[0]);
+    let txid = set_witness.get_auth_data_for_witness();
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let witness = Witness::for_utxo(&genesis_hash, &txid, &key);
+
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    let counter = SpendingCounter::zero();
+    assert!(witness
+        .verify(&genesis_hash, &txid, &address, &counter)
+        .unwrap());
+    let other_hash = Hash::calculate(&[1]);
+    assert!(!witness
+        .verify(&other_hash, &txid, &address, &counter)
+        .unwrap());
+
+    let mut witnesses = Witnesses::new();
+    witnesses.add(witness);
+    let transaction = set_witness
+        .set_witnesses(&witnesses)
+        .set_payload_auth(&PayloadAuthData::for_no_payload())
+        .unwrap();
+
+    // an account resolution for a utxo input is a mistake of the caller
+    let mut resolved = ResolvedInputs::new();
+    resolved.add(ResolvedInput::account(&key.to_public(), &counter));
+    let error = transaction
+        .verify_witnesses(&genesis_hash, &resolved)
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+
+    let mut resolved = ResolvedInputs::new();
+    resolved.add(ResolvedInput::utxo(&address).unwrap());
+    let verification = transaction
+        .verify_witnesses(&genesis_hash, &resolved)
+        .unwrap();
+    assert!(verification.is_valid());
+}
+
+#[test]
+fn witness_verification_missing_witness() {
+    let iobuilder = mock_io_builder(32, 20);
+    let ios = iobuilder.build();
+    let transaction = TransactionBuilder::new()
+        .no_payload()
+        .set_ios(&ios.inputs(), &ios.outputs())
+        .set_witnesses(&Witnesses::new())
+        .set_payload_auth(&PayloadAuthData::for_no_payload())
+        .unwrap();
+
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    let mut resolved = ResolvedInputs::new();
+    resolved.add(ResolvedInput::utxo(&address).unwrap());
+    let verification = transaction
+        .verify_witnesses(&Hash::calculate(&[0]), &resolved)
+        .unwrap();
+    assert_eq!(verification.size(), 1);
+    assert_eq!(verification.get(0), WitnessStatus::Missing);
+    assert!(!verification.is_valid());
+}
+
+#[test]
+fn sign_transaction_with_keys() {
+    let iobuilder = mock_io_builder(32, 20);
+    let ios = iobuilder.build();
+    let builder = TransactionBuilder::new()
+        .no_payload()
+        .set_ios(&ios.inputs(), &ios.outputs());
+
+    let genesis_hash = Hash::calculate(&[0]);
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let mut signers = InputSigners::new();
+    signers.add(InputSigner::utxo(&Signer::from_private_key(&key)));
+    let transaction = builder
+        .sign(&genesis_hash, &signers, &PayloadSigners::new())
+        .unwrap();
+
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    let mut resolved = ResolvedInputs::new();
+    resolved.add(ResolvedInput::utxo(&address).unwrap());
+    assert!(transaction
+        .verify_witnesses(&genesis_hash, &resolved)
+        .unwrap()
+        .is_valid());
+}
+
+#[test]
+fn simple_transaction_builder() {
+    let genesis_hash = Hash::calculate(&[0]);
+    let fee = Fee::linear_fee(&2u64.into(), &1u64.into(), &0u64.into());
+    let mut builder = SimpleTransactionBuilder::with_parameters(&genesis_hash, &fee);
+
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    let txid = FragmentId::calculate(&[0]);
+    builder
+        .add_utxo_input(&UtxoPointer::new(&txid, 0, &100u64.into()), &key)
+        .unwrap();
+    builder.add_output(&address, &50u64.into()).unwrap();
+
+    let fragment = builder.finalize(&OutputPolicy::one(&address)).unwrap();
+    let transaction = fragment.get_transaction().unwrap();
+    assert_eq!(transaction.outputs().size(), 2);
+
+    let mut resolved = ResolvedInputs::new();
+    resolved.add(ResolvedInput::utxo(&address).unwrap());
+    assert!(transaction
+        .verify_witnesses(&genesis_hash, &resolved)
+        .unwrap()
+        .is_valid());
+}
+
+#[test]
+fn stake_pool_leader_keys() {
+    let secrets = GenesisPraosLeader::generate().unwrap();
+    let kes = secrets.kes_secret_key();
+    let vrf = secrets.vrf_secret_key();
+
+    let decoded = KesSecretKey::from_bech32(&kes.to_bech32()).unwrap();
+    assert_eq!(decoded.as_bytes(), kes.as_bytes());
+    let decoded = VrfSecretKey::from_bytes(&vrf.as_bytes()).unwrap();
+    assert_eq!(decoded.to_bech32(), vrf.to_bech32());
+
+    let leader = secrets.leader();
+    assert_eq!(
+        leader.kes_public_key().to_bech32(),
+        kes.to_public().to_bech32()
+    );
+    assert_eq!(
+        leader.vrf_public_key().as_bytes(),
+        vrf.to_public().as_bytes()
+    );
+
+    let mut evolving = kes.clone();
+    assert_eq!(evolving.period(), 0);
+    evolving.update(3).unwrap();
+    assert_eq!(evolving.period(), 3);
+    let error = evolving.update(2).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+}
+
+#[test]
+fn leader_schedule() {
+    let vrf = VrfSecretKey::generate().unwrap();
+    let calculator = LeaderScheduleCalculator::new(&vrf, 1000, 10).unwrap();
+    let nonce = [0u8; 32];
+
+    // with all the stake and every slot active, the pool leads the whole epoch
+    let slots = calculator
+        .schedule(2, &nonce, &100u64.into(), &100u64.into())
+        .unwrap();
+    assert_eq!(slots.size(), 10);
+    assert_eq!(slots.get(3).date(), BlockDate::new(2, 3));
+
+    let error = calculator
+        .schedule(2, &nonce[1..], &100u64.into(), &100u64.into())
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidLength);
+    assert!(LeaderScheduleCalculator::new(&vrf, 0, 10).is_err());
+}
+
+#[test]
+fn genesis_block_header() {
+    let leader = PrivateKey::generate_ed25519().unwrap().to_public();
+    let block0 = mock_block0_builder(&leader).build().unwrap();
+
+    let header = Header::from_bytes(&block0.header().as_bytes().unwrap()).unwrap();
+    assert_eq!(header.id().as_bytes(), block0.id().as_bytes());
+    assert_eq!(header.version(), HeaderVersion::Genesis);
+    assert_eq!(header.chain_length(), 0);
+    assert!(header.bft_leader().is_none());
+    let error = header.verify_bft(&leader).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::WrongType);
+}
+
+#[test]
+fn bft_header_verification() {
+    let leader = PrivateKey::generate_ed25519().unwrap();
+    let block0 = mock_block0_builder(&leader.to_public()).build().unwrap();
+    let block = mock_bft_block(&block0, 1, BlockDate::new(0, 1), &leader, &[]);
+
+    let header = block.header();
+    assert_eq!(header.version(), HeaderVersion::Bft);
+    assert_eq!(header.parent_id().as_bytes(), block0.id().as_bytes());
+    assert_eq!(
+        header.bft_leader().unwrap().as_bytes(),
+        leader.to_public().as_bytes()
+    );
+    assert!(header.verify_bft(&leader.to_public()).unwrap());
+    let other = PrivateKey::generate_ed25519().unwrap().to_public();
+    assert!(!header.verify_bft(&other).unwrap());
+    assert!(header.pool_id().is_none());
+
+    // the content hash starts at the byte 20 and is part of the signed bytes
+    let mut bytes = header.as_bytes().unwrap();
+    bytes[20] ^= 1;
+    let tampered = Header::from_bytes(&bytes).unwrap();
+    assert!(!tampered.verify_bft(&leader.to_public()).unwrap());
+}
+
+#[test]
+fn genesis_praos_header_verification() {
+    let leader = PrivateKey::generate_ed25519().unwrap().to_public();
+    let block0 = mock_block0_builder(&leader).build().unwrap();
+    let secrets = GenesisPraosLeader::generate().unwrap();
+    let pool_hex = "8a7b798c09f4ee16f1348d71a9b534a429f0cec9d6ef5ad9e274b2917167501c";
+    let pool_id = PoolId::from_hex(pool_hex).unwrap();
+
+    let contents = chain::block::Contents::empty();
+    let parent_id = chain::block::HeaderId::deserialize(block0.id().as_bytes().as_slice()).unwrap();
+    let node_id: chain::certificate::PoolId =
+        crypto::Blake2b256::from_str(pool_hex).unwrap().into();
+    let vrf_secret = crypto::SecretKey::<crypto::Curve25519_2HashDH>::from_binary(
+        &secrets.vrf_secret_key().as_bytes(),
+    )
+    .unwrap();
+    let vrf_proof =
+        crypto::vrf_evaluate_and_prove(&vrf_secret, &[0u8; 32], rand_os::OsRng::new().unwrap());
+    let kes_secret = crypto::SecretKey::<crypto::SumEd25519_12>::from_binary(
+        &secrets.kes_secret_key().as_bytes(),
+    )
+    .unwrap();
+    let header =
+        chain::block::HeaderBuilderNew::new(chain::block::BlockVersion::KesVrfproof, &contents)
+            .set_parent(&parent_id, chain::block::ChainLength::from(1))
+            .set_date(chain::block::BlockDate {
+                epoch: 0,
+                slot_id: 1,
+            })
+            .into_genesis_praos_builder()
+            .unwrap()
+            .set_consensus_data(&node_id, &vrf_proof.into())
+            .sign_using(&kes_secret)
+            .generalize();
+    let header = Header::from_bytes(&header.serialize_as_vec().unwrap()).unwrap();
+
+    assert_eq!(header.version(), HeaderVersion::GenesisPraos);
+    assert_eq!(header.pool_id().unwrap().to_string(), pool_hex);
+    assert!(header.vrf_proof().is_some());
+    assert!(header.bft_signature().is_none());
+    let leader_keys = secrets.leader();
+    assert!(header.verify_genesis_praos(&pool_id, &leader_keys).unwrap());
+    let other_pool = PoolId::from_hex(&"00".repeat(32)).unwrap();
+    assert!(!header
+        .verify_genesis_praos(&other_pool, &leader_keys)
+        .unwrap());
+    let other_leader = GenesisPraosLeader::generate().unwrap().leader();
+    assert!(!header
+        .verify_genesis_praos(&pool_id, &other_leader)
+        .unwrap());
+    let error = header.verify_bft(&leader).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::WrongType);
+
+    let mut bytes = header.as_bytes().unwrap();
+    bytes[20] ^= 1;
+    let tampered = Header::from_bytes(&bytes).unwrap();
+    assert!(!tampered
+        .verify_genesis_praos(&pool_id, &leader_keys)
+        .unwrap());
+}
+
+#[test]
+fn header_chain_from_genesis() {
+    let leader = PrivateKey::generate_ed25519().unwrap().to_public();
+    let block0 = mock_block0_builder(&leader).build().unwrap();
+
+    let mut chain = HeaderChain::new(&block0.header());
+    assert_eq!(chain.length(), 0);
+    assert_eq!(chain.add_block(&block0), HeaderStatus::AlreadyKnown);
+    assert_eq!(
+        chain.add_header(&block0.header()),
+        HeaderStatus::AlreadyKnown
+    );
+    assert_eq!(chain.size(), 1);
+    assert!(chain.is_in_main_chain(&block0.id()));
+    assert_eq!(chain.tip().id().as_bytes(), block0.id().as_bytes());
+    assert!(chain.get(&block0.id()).is_some());
+}
+
+#[test]
+fn header_chain_statuses() {
+    let leader = PrivateKey::generate_ed25519().unwrap();
+    let block0 = mock_block0_builder(&leader.to_public()).build().unwrap();
+    let mut chain = HeaderChain::new(&block0.header());
+    let date = |slot| BlockDate::new(0, slot);
+
+    let b1 = mock_bft_block(&block0, 1, date(1), &leader, &[]);
+    let b2 = mock_bft_block(&b1, 2, date(2), &leader, &[]);
+    assert_eq!(chain.add_block(&b1), HeaderStatus::Tip);
+    assert_eq!(chain.add_header(&b2.header()), HeaderStatus::Tip);
+    assert_eq!(chain.add_block(&b2), HeaderStatus::AlreadyKnown);
+    assert_eq!(chain.length(), 2);
+
+    // a fork as long as the main chain doesn't change the tip
+    let f1 = mock_bft_block(&block0, 1, date(5), &leader, &[]);
+    let f2 = mock_bft_block(&f1, 2, date(6), &leader, &[]);
+    assert_eq!(chain.add_block(&f1), HeaderStatus::Fork);
+    assert_eq!(chain.add_block(&f2), HeaderStatus::Fork);
+    assert_eq!(chain.tip().id().as_bytes(), b2.id().as_bytes());
+    assert!(!chain.is_in_main_chain(&f1.id()));
+
+    // the fork becomes the longest branch
+    let f3 = mock_bft_block(&f2, 3, date(7), &leader, &[]);
+    assert_eq!(chain.add_block(&f3), HeaderStatus::Tip);
+    assert_eq!(chain.tip().id().as_bytes(), f3.id().as_bytes());
+    assert_eq!(chain.length(), 3);
+    assert!(chain.is_in_main_chain(&f1.id()));
+    assert!(chain.is_in_main_chain(&block0.id()));
+    assert!(!chain.is_in_main_chain(&b2.id()));
+    assert_eq!(chain.size(), 6);
+
+    let unknown = mock_bft_block(&block0, 1, date(9), &leader, &[]);
+    let orphan = mock_bft_block(&unknown, 2, date(10), &leader, &[]);
+    assert_eq!(chain.add_block(&orphan), HeaderStatus::UnknownParent);
+    let skipping = mock_bft_block(&b2, 4, date(3), &leader, &[]);
+    assert_eq!(chain.add_block(&skipping), HeaderStatus::WrongChainLength);
+    let same_date = mock_bft_block(&b2, 3, date(2), &leader, &[]);
+    assert_eq!(chain.add_block(&same_date), HeaderStatus::DateNotIncreasing);
+
+    // the header announces a fragment the block doesn't have
+    let initial = block0.fragments().get(0);
+    let announced = mock_bft_block(&b2, 3, date(3), &leader, &[&initial]);
+    let header =
+        chain::block::Header::deserialize(announced.header().as_bytes().unwrap().as_slice())
+            .unwrap();
+    let contents = chain::block::Contents::empty();
+    let mismatch: Block = chain::block::Block { header, contents }.into();
+    assert_eq!(chain.add_block(&mismatch), HeaderStatus::ContentMismatch);
+    assert_eq!(chain.add_block(&announced), HeaderStatus::Fork);
+    assert_eq!(chain.size(), 7);
+    assert_eq!(chain.tip().id().as_bytes(), f3.id().as_bytes());
+}
+
+#[test]
+fn block_contents_proof() {
+    let leader = PrivateKey::generate_ed25519().unwrap();
+    let block0 = mock_block0_builder(&leader.to_public()).build().unwrap();
+    assert!(block0.verify_content());
+
+    let fragment_id = block0.fragments().get(0).id();
+    let proof = block0.contents_proof(&fragment_id).unwrap();
+    let contents = BlockContentsProof::from_bytes(&proof.as_bytes()).unwrap();
+    assert_eq!(
+        contents.as_bytes().len() as u32,
+        block0.header().content_size()
+    );
+    assert!(contents.verify(&block0.header(), &fragment_id));
+    assert!(contents.fragment(&fragment_id).is_some());
+
+    let unknown = FragmentId::calculate(&[0u8; 4]);
+    assert!(!contents.verify(&block0.header(), &unknown));
+    let error = block0.contents_proof(&unknown).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidArgument);
+
+    // the fragments of another block don't match the header
+    let block = mock_bft_block(&block0, 1, BlockDate::new(0, 1), &leader, &[]);
+    assert!(!contents.verify(&block.header(), &fragment_id));
+}
+
+#[test]
+fn block_view_and_stream_decoder() {
+    let leader = PrivateKey::generate_ed25519().unwrap().to_public();
+    let block0 = mock_block0_builder(&leader).build().unwrap();
+    let bytes = block0.as_bytes().unwrap();
+
+    let view = BlockView::new(&bytes).unwrap();
+    assert_eq!(
+        view.header().unwrap().id().as_bytes(),
+        block0.id().as_bytes()
+    );
+    let fragments = view.fragments().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(fragments.len(), block0.fragments().size());
+    assert_eq!(
+        fragments[0].id().as_bytes(),
+        block0.fragments().get(0).id().as_bytes()
+    );
+    assert!(view
+        .fragments()
+        .all(|fragment| fragment.unwrap().to_fragment().is_ok()));
+
+    let mut stream = Vec::new();
+    for _ in 0..2 {
+        stream.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        stream.extend_from_slice(&bytes);
+    }
+    let mut decoder = BlockStreamDecoder::new();
+    decoder.push(&stream[..bytes.len()]);
+    assert!(decoder.next_block().unwrap().is_none());
+    decoder.push(&stream[bytes.len()..]);
+    for _ in 0..2 {
+        let block = decoder.next_block().unwrap().unwrap();
+        assert_eq!(block.id().as_bytes(), block0.id().as_bytes());
+    }
+    assert!(decoder.next_block().unwrap().is_none());
+    assert_eq!(decoder.pending_size(), 0);
+
+    // a corrupted length prefix is refused instead of waiting for 4GB
+    let mut decoder = BlockStreamDecoder::new();
+    decoder.push(&[0xff, 0xff, 0xff, 0xff, 0x00]);
+    let error = decoder.next_block().err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::Deserialization);
+    assert_eq!(decoder.pending_size(), 5);
+}
+
+#[test]
+fn history_of_initial_funds() {
+    let leader = PrivateKey::generate_ed25519().unwrap().to_public();
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    let other = Address::single_from_public_key(
+        &PrivateKey::generate_ed25519().unwrap().to_public(),
+        AddressDiscrimination::Test,
+    );
+    let mut builder = mock_block0_builder(&leader);
+    builder.add_initial_fund(&Output::new(&address, &Value::from_str("1000").unwrap()));
+    builder.add_initial_fund(&Output::new(&other, &Value::from_str("500").unwrap()));
+    let block0 = builder.build().unwrap();
+
+    let mut history = HistoryBuilder::new(AddressDiscrimination::Test);
+    history.watch_address(&address);
+    let records = history.add_block(&block0);
+    assert_eq!(records.size(), 1);
+    let record = records.get(0);
+    assert_eq!(record.direction(), TransactionDirection::Incoming);
+    assert_eq!(record.received().to_str(), "1000");
+    assert_eq!(record.sent().to_str(), "0");
+    assert_eq!(record.net_change(), "1000");
+    assert_eq!(record.fee().to_str(), "0");
+    assert!(record.certificate_kind().is_none());
+    assert_eq!(history.records().size(), 1);
+}
+
+#[test]
+fn history_of_spent_funds() {
+    let leader = PrivateKey::generate_ed25519().unwrap();
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    let account_key = PrivateKey::generate_ed25519().unwrap();
+    let account = Account::single_from_public_key(&account_key.to_public());
+    let other = Address::single_from_public_key(
+        &PrivateKey::generate_ed25519().unwrap().to_public(),
+        AddressDiscrimination::Test,
+    );
+    let mut builder = mock_block0_builder(&leader.to_public());
+    builder.add_initial_fund(&Output::new(&address, &1000u64.into()));
+    builder.add_initial_fund(&Output::new(
+        &account.to_address(AddressDiscrimination::Test),
+        &1000u64.into(),
+    ));
+    let block0 = builder.build().unwrap();
+    let fund = block0.fragments().get(1).id();
+
+    let fee = Fee::linear_fee(&10u64.into(), &0u64.into(), &0u64.into());
+    let genesis_hash = Hash::calculate(&[0]);
+    let mut outgoing = SimpleTransactionBuilder::with_parameters(&genesis_hash, &fee);
+    outgoing
+        .add_utxo_input(&UtxoPointer::new(&fund, 0, &1000u64.into()), &key)
+        .unwrap();
+    outgoing.add_output(&other, &500u64.into()).unwrap();
+    outgoing.add_output(&address, &490u64.into()).unwrap();
+    let outgoing = outgoing.finalize(&OutputPolicy::forget()).unwrap();
+
+    let mut self_transfer = SimpleTransactionBuilder::with_parameters(&genesis_hash, &fee);
+    self_transfer
+        .add_account_input(&account_key, &310u64.into(), &SpendingCounter::zero())
+        .unwrap();
+    self_transfer.add_output(&address, &300u64.into()).unwrap();
+    let self_transfer = self_transfer.finalize(&OutputPolicy::forget()).unwrap();
+
+    let pool_id =
+        PoolId::from_hex("8a7b798c09f4ee16f1348d71a9b534a429f0cec9d6ef5ad9e274b2917167501c")
+            .unwrap();
+    let delegation =
+        StakeDelegation::new(&DelegationType::full(&pool_id), &account_key.to_public());
+    let mut signers = PayloadSigners::new();
+    signers.add(PayloadSigner::account(&Signer::from_private_key(
+        &account_key,
+    )));
+    let mut delegating = SimpleTransactionBuilder::with_parameters(&genesis_hash, &fee);
+    delegating
+        .add_account_input(&account_key, &10u64.into(), &SpendingCounter::from_u32(1))
+        .unwrap();
+    delegating
+        .set_certificate(&Certificate::stake_delegation(&delegation), &signers)
+        .unwrap();
+    let delegating = delegating.finalize(&OutputPolicy::forget()).unwrap();
+
+    let block1 = mock_bft_block(
+        &block0,
+        1,
+        BlockDate::new(0, 1),
+        &leader,
+        &[&outgoing, &self_transfer, &delegating],
+    );
+
+    let mut history = HistoryBuilder::new(AddressDiscrimination::Test);
+    history.watch_address(&address);
+    history.watch_account(&account);
+    let records = history.add_block(&block0);
+    assert_eq!(records.size(), 1);
+    assert_eq!(records.get(0).received().to_str(), "2000");
+
+    let records = history.add_block(&block1);
+    assert_eq!(records.size(), 3);
+
+    // the utxo of the genesis block is recognized when spent
+    let record = records.get(0);
+    assert_eq!(record.fragment_id().as_bytes(), outgoing.id().as_bytes());
+    assert_eq!(record.date(), BlockDate::new(0, 1));
+    assert_eq!(record.direction(), TransactionDirection::Outgoing);
+    assert_eq!(record.sent().to_str(), "1000");
+    assert_eq!(record.received().to_str(), "490");
+    assert_eq!(record.fee().to_str(), "10");
+    assert_eq!(record.net_change(), "-510");
+    assert_eq!(record.counterparties().size(), 1);
+    assert_eq!(record.counterparties().get(0).as_bytes(), other.as_bytes());
+    assert!(record.certificate_kind().is_none());
+
+    let record = records.get(1);
+    assert_eq!(record.direction(), TransactionDirection::SelfTransfer);
+    assert_eq!(record.sent().to_str(), "310");
+    assert_eq!(record.received().to_str(), "300");
+    assert_eq!(record.fee().to_str(), "10");
+    assert_eq!(record.net_change(), "-10");
+    assert_eq!(record.counterparties().size(), 0);
+
+    let record = records.get(2);
+    assert_eq!(record.sent().to_str(), "10");
+    assert_eq!(record.fee().to_str(), "10");
+    assert_eq!(record.net_change(), "-10");
+    assert_eq!(
+        record.certificate_kind(),
+        Some(CertificateKind::StakeDelegation)
+    );
+
+    assert_eq!(history.records().size(), 4);
+}
+
+#[test]
+fn utxo_store_rollback() {
+    let leader = PrivateKey::generate_ed25519().unwrap();
+    let key = PrivateKey::generate_ed25519().unwrap();
+    let address = Address::single_from_public_key(&key.to_public(), AddressDiscrimination::Test);
+    let other = Address::single_from_public_key(
+        &PrivateKey::generate_ed25519().unwrap().to_public(),
+        AddressDiscrimination::Test,
+    );
+    let mut builder = mock_block0_builder(&leader.to_public());
+    builder.add_initial_fund(&Output::new(&address, &1000u64.into()));
+    let block0 = builder.build().unwrap();
+    let fund = block0.fragments().get(1).id();
+
+    let fee = Fee::linear_fee(&0u64.into(), &0u64.into(), &0u64.into());
+    let mut transaction = SimpleTransactionBuilder::with_parameters(&Hash::calculate(&[0]), &fee);
+    transaction
+        .add_utxo_input(&UtxoPointer::new(&fund, 0, &1000u64.into()), &key)
+        .unwrap();
+    transaction.add_output(&other, &400u64.into()).unwrap();
+    transaction.add_output(&address, &600u64.into()).unwrap();
+    let fragment = transaction.finalize(&OutputPolicy::forget()).unwrap();
+    let block1 = mock_bft_block(&block0, 1, BlockDate::new(0, 1), &leader, &[&fragment]);
+
+    let mut store = UtxoStore::new();
+    store.watch_address(&address);
+    store.apply_block(&block0).unwrap();
+    store.apply_block(&block1).unwrap();
+    assert_eq!(store.balance(&address).to_str(), "600");
+    let utxo = store.utxos(&address).get(0);
+    assert_eq!(utxo.fragment_id().as_bytes(), fragment.id().as_bytes());
+    assert_eq!(utxo.output_index(), 1);
+
+    store.rollback_to(&block0.id()).unwrap();
+    assert_eq!(store.tip().unwrap().as_bytes(), block0.id().as_bytes());
+    assert_eq!(store.balance(&address).to_str(), "1000");
+    assert_eq!(store.utxos(&address).size(), 1);
+    assert_eq!(
+        store.utxos(&address).get(0).fragment_id().as_bytes(),
+        fund.as_bytes()
+    );
+
+    // only the tip can be reverted to once the older blocks are pruned
+    store.apply_block(&block1).unwrap();
+    store.set_max_rollback_depth(0);
+    let error = store.rollback_to(&block0.id()).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidBlock);
+    assert_eq!(store.balance(&address).to_str(), "600");
+}
+
+#[test]
+fn error_source_message() {
+    let error = Address::from_string("not an address").err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidAddress);
+    assert!(error.source_message().is_some());
+    assert!(std::error::Error::source(&error).is_none());
+}
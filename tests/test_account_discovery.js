@@ -0,0 +1,86 @@
+import { expect } from 'chai';
+import { mockBlock0Builder } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+const phrase =
+  'legal winner thank year wave sausage worth useful legal winner thank yellow';
+
+it('derives the address chains up to the gap limit', async () => {
+  const {
+    AccountDiscovery,
+    Address,
+    AddressDiscrimination,
+    Bip32PrivateKey,
+    DerivationPath,
+    DerivationPurpose
+  } = await rust;
+
+  const accountKey = Bip32PrivateKey.from_mnemonic(phrase, new Uint8Array())
+    .derive_path(DerivationPath.account(DerivationPurpose.Cip1852, 0))
+    .to_public();
+
+  const discovery = AccountDiscovery.single(
+    accountKey,
+    AddressDiscrimination.Test,
+    5
+  );
+
+  expect(discovery.external_addresses().size()).to.eql(5);
+  expect(discovery.internal_addresses().size()).to.eql(5);
+  expect(discovery.used_addresses().size()).to.eql(0);
+
+  const expected = Address.single_from_public_key(
+    accountKey
+      .derive(0)
+      .derive(0)
+      .to_raw_key(),
+    AddressDiscrimination.Test
+  );
+  const next = discovery.next_external_address();
+  expect(next.to_string('ta')).to.eql(expected.to_string('ta'));
+  expect(discovery.is_used(next)).to.eql(false);
+  expect(discovery.address_path(next).to_string()).to.eql('m/0/0');
+});
+
+it('extends the chain after an address is seen on the blockchain', async () => {
+  const {
+    AccountDiscovery,
+    AddressDiscrimination,
+    Bip32PrivateKey,
+    DerivationPath,
+    DerivationPurpose,
+    Output,
+    Value
+  } = await rust;
+
+  const accountKey = Bip32PrivateKey.from_mnemonic(phrase, new Uint8Array())
+    .derive_path(DerivationPath.account(DerivationPurpose.Cip1852, 0))
+    .to_public();
+  const discovery = AccountDiscovery.single(
+    accountKey,
+    AddressDiscrimination.Test,
+    5
+  );
+  const paid = discovery.external_addresses().get(3);
+
+  const builder = await mockBlock0Builder();
+  builder.add_initial_fund(Output.new(paid, Value.from_str('1000')));
+  const block0 = builder.build();
+
+  expect(discovery.scan_block(block0)).to.eql(true);
+  expect(discovery.is_used(paid)).to.eql(true);
+  expect(discovery.used_addresses().size()).to.eql(1);
+  expect(discovery.used_addresses().get(0).to_string('ta')).to.eql(
+    paid.to_string('ta')
+  );
+  // the gap of 5 unused addresses is kept after the used one
+  expect(discovery.external_addresses().size()).to.eql(9);
+  expect(discovery.internal_addresses().size()).to.eql(5);
+  expect(
+    discovery.address_path(discovery.next_external_address()).to_string()
+  ).to.eql('m/0/4');
+
+  // scanning the same block again finds nothing new
+  expect(discovery.scan_block(block0)).to.eql(false);
+});
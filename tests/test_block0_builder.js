@@ -0,0 +1,102 @@
+import { expect } from 'chai';
+
+const rust = import('../pkg/js_chain_libs');
+
+const leaderKey =
+  'ed25519_pk1e0rueku628h2fex8pzp48sdpjqku76zlwwgefhyl4lexkl6zugvs0uuy0w';
+const fundedAddress =
+  'ca1q5nr5pvt9e5p009strshxndrsx5etcentslp2rwj6csm8sfk24a2w3swacn';
+
+it('builds a block0 that round-trips', async () => {
+  const {
+    Address,
+    AddressDiscrimination,
+    Block,
+    Block0Builder,
+    ConsensusVersion,
+    Fee,
+    LedgerSimulator,
+    NetworkSettings,
+    Output,
+    PublicKey,
+    Value
+  } = await rust;
+
+  const builder = Block0Builder.new(
+    AddressDiscrimination.Test,
+    ConsensusVersion.Bft,
+    1576000000,
+    2,
+    100
+  );
+  builder.set_fee(
+    Fee.linear_fee(
+      Value.from_str('10'),
+      Value.from_str('2'),
+      Value.from_str('5')
+    )
+  );
+  builder.set_kes_update_speed(43200);
+  builder.add_bft_leader(PublicKey.from_bech32(leaderKey));
+  builder.add_initial_fund(
+    Output.new(Address.from_string(fundedAddress), Value.from_str('1000000'))
+  );
+
+  const block0 = builder.build();
+  const decoded = Block.from_bytes(block0.as_bytes());
+  expect(decoded.id().as_bytes()).to.eql(block0.id().as_bytes());
+  expect(decoded.fragments().size()).to.eql(2);
+  expect(decoded.fragments().get(0).is_initial()).to.eql(true);
+
+  const settings = NetworkSettings.from_block0(decoded);
+  expect(settings.block0_hash().as_bytes()).to.eql(block0.id().as_bytes());
+  expect(settings.block0_date()).to.eql(1576000000);
+  expect(settings.discrimination()).to.eql(AddressDiscrimination.Test);
+  expect(settings.consensus_version()).to.eql(ConsensusVersion.Bft);
+  expect(settings.slot_duration()).to.eql(2);
+  expect(settings.slots_per_epoch()).to.eql(100);
+  expect(settings.kes_update_speed()).to.eql(43200);
+  expect(settings.bft_leaders().size()).to.eql(1);
+  expect(settings.initial_funds().size()).to.eql(1);
+  expect(
+    settings
+      .initial_funds()
+      .get(0)
+      .value()
+      .to_str()
+  ).to.eql('1000000');
+  expect(settings.legacy_funds().size()).to.eql(0);
+
+  const simulator = LedgerSimulator.new(decoded);
+  expect(simulator.tip().as_bytes()).to.eql(block0.id().as_bytes());
+});
+
+it('requires a leader for bft', async () => {
+  const { AddressDiscrimination, Block0Builder, ConsensusVersion } = await rust;
+
+  const builder = Block0Builder.new(
+    AddressDiscrimination.Test,
+    ConsensusVersion.Bft,
+    1576000000,
+    2,
+    100
+  );
+  expect(() => builder.build()).to.throw();
+});
+
+it('requires the genesis praos parameters', async () => {
+  const { AddressDiscrimination, Block0Builder, ConsensusVersion } = await rust;
+
+  const builder = Block0Builder.new(
+    AddressDiscrimination.Test,
+    ConsensusVersion.GenesisPraos,
+    1576000000,
+    2,
+    100
+  );
+  expect(() => builder.build()).to.throw();
+  builder.set_active_slots_coeff(100);
+  expect(() => builder.build()).to.throw();
+  builder.set_kes_update_speed(43200);
+  expect(builder.build().chain_length()).to.eql(0);
+});
@@ -0,0 +1,146 @@
+import { expect } from 'chai';
+
+const rust = import('../pkg/js_chain_libs');
+
+const inputAddress =
+  'ca1qh9u0nxmnfg7af8ycuygx57p5xgzmnmgtaeer9xun7hly6mlgt3pj2xk344';
+const outputAddress =
+  'ca1q5nr5pvt9e5p009strshxndrsx5etcentslp2rwj6csm8sfk24a2w3swacn';
+
+const setup = async () => {
+  const {
+    Account,
+    Address,
+    Fee,
+    Input,
+    Inputs,
+    Output,
+    Outputs,
+    OutputPolicy,
+    Payload,
+    Value
+  } = await rust;
+
+  const account = Account.from_address(Address.from_string(inputAddress));
+  const available = Inputs.new();
+  ['1000', '300', '235', '50', '25'].forEach(value =>
+    available.add(Input.from_account(account, Value.from_str(value)))
+  );
+
+  const outputs = Outputs.new();
+  outputs.add(
+    Output.new(Address.from_string(outputAddress), Value.from_str('500'))
+  );
+
+  // 20 + 5 * (#inputs + #outputs)
+  const fee = Fee.linear_fee(
+    Value.from_str('20'),
+    Value.from_str('5'),
+    Value.from_str('10')
+  );
+
+  const policy = OutputPolicy.one(Address.from_string(inputAddress));
+
+  return { available, outputs, fee, policy, payload: Payload.no_payload() };
+};
+
+const inputValues = inputs => {
+  const values = [];
+  for (let i = 0; i < inputs.size(); i += 1) {
+    values.push(parseInt(inputs.get(i).value().to_str(), 10));
+  }
+  return values;
+};
+
+it('largest first selects the biggest inputs', async () => {
+  const { CoinSelection, CoinSelectionStrategy } = await rust;
+  const { available, outputs, fee, policy, payload } = await setup();
+
+  const selection = CoinSelection.select(
+    CoinSelectionStrategy.LargestFirst,
+    available,
+    outputs,
+    payload,
+    fee,
+    policy
+  );
+
+  expect(inputValues(selection.inputs())).to.eql([1000]);
+  // one input, one output and the change output
+  expect(selection.fee().to_str()).to.eql('35');
+  expect(selection.change().to_str()).to.eql('465');
+  expect(selection.change_address().to_string('ca')).to.eql(inputAddress);
+});
+
+it('random improve covers the outputs and the fee', async () => {
+  const { CoinSelection, CoinSelectionStrategy } = await rust;
+  const { available, outputs, fee, policy, payload } = await setup();
+
+  const selection = CoinSelection.select(
+    CoinSelectionStrategy.RandomImprove,
+    available,
+    outputs,
+    payload,
+    fee,
+    policy
+  );
+
+  const total = inputValues(selection.inputs()).reduce((a, b) => a + b, 0);
+  const paid =
+    500 +
+    parseInt(selection.fee().to_str(), 10) +
+    parseInt(selection.change().to_str(), 10);
+  expect(total).to.eql(paid);
+});
+
+it('branch and bound finds an exact match', async () => {
+  const { CoinSelection, CoinSelectionStrategy } = await rust;
+  const { available, outputs, fee, policy, payload } = await setup();
+
+  const selection = CoinSelection.select(
+    CoinSelectionStrategy.BranchAndBound,
+    available,
+    outputs,
+    payload,
+    fee,
+    policy
+  );
+
+  expect(inputValues(selection.inputs()).sort()).to.eql([235, 300]);
+  expect(selection.fee().to_str()).to.eql('35');
+  expect(selection.change().to_str()).to.eql('0');
+  expect(selection.change_address()).to.eql(undefined);
+
+  const ios = selection
+    .to_builder()
+    .seal_with_output_policy(payload, fee, policy);
+  expect(ios.outputs().size()).to.eql(1);
+});
+
+it('fails when the inputs are not enough', async () => {
+  const {
+    Address,
+    CoinSelection,
+    CoinSelectionStrategy,
+    Output,
+    Outputs,
+    Value
+  } = await rust;
+  const { available, fee, policy, payload } = await setup();
+
+  const outputs = Outputs.new();
+  outputs.add(
+    Output.new(Address.from_string(outputAddress), Value.from_str('2000'))
+  );
+
+  expect(() =>
+    CoinSelection.select(
+      CoinSelectionStrategy.LargestFirst,
+      available,
+      outputs,
+      payload,
+      fee,
+      policy
+    )
+  ).to.throw();
+});
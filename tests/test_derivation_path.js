@@ -0,0 +1,85 @@
+import { expect } from 'chai';
+
+const rust = import('../pkg/js_chain_libs');
+
+const phrase =
+  'legal winner thank year wave sausage worth useful legal winner thank yellow';
+
+it('parses and prints derivation paths', async () => {
+  const {
+    DerivationPath,
+    DerivationPurpose,
+    DerivationRole
+  } = await rust;
+
+  const path = DerivationPath.from_string("m/44'/1815'/0'/2/0");
+  expect(path.to_string()).to.eql("m/44'/1815'/0'/2/0");
+  expect(path.size()).to.eql(5);
+  expect(path.get(1)).to.eql(0x80000000 + 1815);
+  expect(path.is_hardened(2)).to.eql(true);
+  expect(path.is_hardened(3)).to.eql(false);
+
+  expect(DerivationPath.from_string('m/1852h/1815h/0h').to_string()).to.eql(
+    "m/1852'/1815'/0'"
+  );
+  expect(
+    DerivationPath.address(
+      DerivationPurpose.Cip1852,
+      0,
+      DerivationRole.Internal,
+      3
+    ).to_string()
+  ).to.eql("m/1852'/1815'/0'/1/3");
+
+  expect(() => DerivationPath.from_string("44'/0")).to.throw();
+  expect(() => DerivationPath.from_string('m/a')).to.throw();
+});
+
+it('derives keys through a path', async () => {
+  const {
+    Bip32PrivateKey,
+    DerivationPath,
+    DerivationPurpose,
+    DerivationRole
+  } = await rust;
+
+  const root = Bip32PrivateKey.from_mnemonic(phrase, new Uint8Array());
+  const accountPath = DerivationPath.account(DerivationPurpose.Cip1852, 0);
+  const account = root.derive_path(accountPath);
+
+  expect(account.to_bech32()).to.eql(
+    root
+      .derive(0x80000000 + 1852)
+      .derive(0x80000000 + 1815)
+      .derive(0x80000000)
+      .to_bech32()
+  );
+
+  const chainPath = DerivationPath.from_string('m/0/7');
+  expect(
+    account
+      .to_public()
+      .derive_path(chainPath)
+      .to_bech32()
+  ).to.eql(
+    account
+      .derive_path(chainPath)
+      .to_public()
+      .to_bech32()
+  );
+
+  expect(() => root.to_public().derive_path(accountPath)).to.throw();
+
+  expect(
+    root
+      .derive_path(
+        DerivationPath.address(
+          DerivationPurpose.Cip1852,
+          0,
+          DerivationRole.External,
+          7
+        )
+      )
+      .to_bech32()
+  ).to.eql(account.derive(0).derive(7).to_bech32());
+});
@@ -0,0 +1,42 @@
+import { expect } from 'chai';
+import { thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+it('throws errors with a kind and a message', async () => {
+  const { Address, Value, DerivationPath, ErrorKind } = await rust;
+
+  const addressError = thrownBy(() => Address.from_string('not an address'));
+  expect(addressError.kind()).to.eql(ErrorKind.InvalidAddress);
+  expect(addressError.message()).to.eql('Invalid address');
+  expect(addressError.source()).to.be.a('string');
+  expect(addressError.toString()).to.contain('Invalid address: ');
+
+  const valueError = thrownBy(() => Value.from_str('not a number'));
+  expect(valueError.kind()).to.eql(ErrorKind.InvalidValue);
+
+  const pathError = thrownBy(() => DerivationPath.from_string('m/a'));
+  expect(pathError.kind()).to.eql(ErrorKind.Derivation);
+  expect(pathError.source()).to.eql(undefined);
+});
+
+it('reports a mismatching payload auth', async () => {
+  const {
+    TransactionBuilder,
+    Inputs,
+    Outputs,
+    Witnesses,
+    PayloadAuthData,
+    ErrorKind
+  } = await rust;
+
+  const builder = new TransactionBuilder()
+    .no_payload()
+    .set_ios(Inputs.new(), Outputs.new())
+    .set_witnesses(Witnesses.new());
+
+  const error = thrownBy(() =>
+    builder.set_payload_auth(PayloadAuthData.for_owner_stake_delegation())
+  );
+  expect(error.kind()).to.eql(ErrorKind.WrongPayloadAuth);
+});
@@ -0,0 +1,80 @@
+import { expect } from 'chai';
+import { mockBlock0Builder, thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+// signed by a stake pool
+const binaryBlock =
+  '02b6000200000099000000000000000300000001ceb579548cdbfebca14ba55a6d68447f94f0d2e784a1425cd07e848bd311178d0a8195c1876d5f5e80a25412c1c7d47e06048553dbbb47d89f9451f3799e54b238502fef71ee8bdebabcd61f57ad91b02e3fde1c686911fa05a4f0a501d97ebdd6f8cf1b5eaf07d1bf489baf7b9a5c765c1e94524e2c76728112dc41df5fc80add667dcb347c8a8e52fb27cfa22203a0c2f93e3074ee058f2da0b9b27bbf49067b00395beca49a944d273f2f20dd3b97456568697a3c1f6e06af2d82e03fb10c000000004206b845fcfb722382c01139040c7c5c336b93263f37023bf07ac32dd8de121d35f3e6552b4694154daea0a5a52fb58249f34ccbcae10af5a42450338a42150346e198c21bae0aec3822adc011d5b0390650b60e55b6fb5662f89ec2cbda746c202c6ab2f633a962f4fdecff78e1f8cc6b0279fc6537aa042bb9842536c9b690e750719750afe8a06008d5a4b87d15612efea059764d0b172057d99404bbb24d41b88488ae2900e8d0b1fc5ad85f20e685a203c39fc890076774768ccc7a31f0a337b4deb1a30bb4e275e4af3fa8e91cd6be07077f3b19cd5a12292ae77c1d9a1ae88f057baece8ca01c9acd637dc022458d067dc5b832126b1a0527f56b20576a699d3423636d0ad46fd7ad2e0b7450a4eb6aaaf4ffaefa480f2da028fc93e56d327218f01d169834dfc87e352f07bb3c136a57a1bc7c46a3ecf9fad8b52254b0c5fc6107c0dc4813b608cac8fb562b91db3fe75e235a33fe86aabd157f2202ab7d964ce3b8849bc4c2fa53261abce219fd67086a490a562813f0b6eec3dbb4cd698ff11b1163995b6de6c99624557dc552be395d8d8ac618e8b5a8c565e553fcc52689cac7e5ff19d9a508dfa1037119f77d5fc3780e2ec9bbe1bbfaa257f69fa56031c7f77862d384576fc46e522e2596715475e9b421f92045be26b3350d009700020101ff000000000000271a5ca751423e00439b7389dbbad1a346a3ef8438d73a15528c44677160e60d2b8f85b7b0199dcc3b976ba44603685c707e56778efabf17617d7ab69a1465c4e8dccf0000000000002710025e7ee0802332cb51c1e527bd8db6800a38d2c4b91a4c4671399a73b763534e0ec928de0d4b27faba3c1f82f9fbb1e67dc901a42eed47862e80d6c0a96aaa4e07';
+
+it('reads the header of a genesis block', async () => {
+  const {
+    ErrorKind,
+    Header,
+    HeaderVersion,
+    PrivateKey
+  } = await rust;
+
+  const leader = PrivateKey.generate_ed25519().to_public();
+  const builder = await mockBlock0Builder(leader);
+  const block0 = builder.build();
+
+  const header = Header.from_bytes(block0.header().as_bytes());
+  expect(header.id().as_bytes()).to.eql(block0.id().as_bytes());
+  expect(header.version()).to.eql(HeaderVersion.Genesis);
+  expect(header.chain_length()).to.eql(0);
+  expect(header.date().epoch()).to.eql(0);
+  expect(header.date().slot()).to.eql(0);
+  expect(header.bft_leader()).to.eql(undefined);
+  expect(header.pool_id()).to.eql(undefined);
+  expect(header.bft_signature()).to.eql(undefined);
+  expect(thrownBy(() => header.verify_bft(leader)).kind()).to.eql(
+    ErrorKind.WrongType
+  );
+});
+
+it('reads the header of a stake pool block', async () => {
+  const {
+    Block,
+    ErrorKind,
+    GenesisPraosLeader,
+    Header,
+    HeaderVersion,
+    PrivateKey
+  } = await rust;
+
+  const block = Block.from_bytes(hexStringToBytes(binaryBlock));
+  const header = block.header();
+  expect(Header.from_bytes(header.as_bytes()).id().as_bytes()).to.eql(
+    block.id().as_bytes()
+  );
+  expect(header.version()).to.eql(HeaderVersion.GenesisPraos);
+  expect(header.chain_length()).to.eql(1);
+  expect(header.date().epoch()).to.eql(0);
+  expect(header.date().slot()).to.eql(3);
+  expect(header.content_size()).to.eql(0x99);
+  expect(header.parent_id().as_bytes().length).to.eql(32);
+  expect(header.content_hash().as_bytes().length).to.eql(32);
+  expect(header.bft_leader()).to.eql(undefined);
+  expect(header.pool_id()).to.not.eql(undefined);
+  expect(header.vrf_proof()).to.not.eql(undefined);
+  expect(header.kes_signature()).to.not.eql(undefined);
+  expect(header.signed_bytes().length).to.be.below(
+    header.as_bytes().length
+  );
+
+  // the keys of the pool are not known, any other leader is refused
+  const other = GenesisPraosLeader.generate().leader();
+  expect(header.verify_genesis_praos(header.pool_id(), other)).to.eql(false);
+  const error = thrownBy(() =>
+    header.verify_bft(PrivateKey.generate_ed25519().to_public())
+  );
+  expect(error.kind()).to.eql(ErrorKind.WrongType);
+});
+
+function hexStringToBytes(string) {
+  const bytes = [];
+  for (let c = 0; c < string.length; c += 2)
+    bytes.push(parseInt(string.substr(c, 2), 16));
+  return Uint8Array.from(bytes);
+}
@@ -0,0 +1,70 @@
+import { expect } from 'chai';
+import { mockBlock0Builder } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+it('records the initial funds of a wallet', async () => {
+  const {
+    Account,
+    Address,
+    AddressDiscrimination,
+    HistoryBuilder,
+    Output,
+    PrivateKey,
+    TransactionDirection,
+    Value
+  } = await rust;
+
+  const address = Address.single_from_public_key(
+    PrivateKey.generate_ed25519().to_public(),
+    AddressDiscrimination.Test
+  );
+  const account = Account.single_from_public_key(
+    PrivateKey.generate_ed25519().to_public()
+  );
+  const other = Address.single_from_public_key(
+    PrivateKey.generate_ed25519().to_public(),
+    AddressDiscrimination.Test
+  );
+
+  const builder = await mockBlock0Builder();
+  builder.add_initial_fund(Output.new(address, Value.from_str('1000')));
+  builder.add_initial_fund(
+    Output.new(
+      account.to_address(AddressDiscrimination.Test),
+      Value.from_str('200')
+    )
+  );
+  builder.add_initial_fund(Output.new(other, Value.from_str('500')));
+  const block0 = builder.build();
+
+  const history = HistoryBuilder.new(AddressDiscrimination.Test);
+  history.watch_address(address);
+  history.watch_account(account);
+  const records = history.add_block(block0);
+  expect(records.size()).to.eql(1);
+
+  const record = records.get(0);
+  expect(record.fragment_id().as_bytes()).to.eql(
+    block0
+      .fragments()
+      .get(1)
+      .id()
+      .as_bytes()
+  );
+  expect(record.date().epoch()).to.eql(0);
+  expect(record.date().slot()).to.eql(0);
+  expect(record.direction()).to.eql(TransactionDirection.Incoming);
+  expect(record.received().to_str()).to.eql('1200');
+  expect(record.sent().to_str()).to.eql('0');
+  expect(record.net_change()).to.eql('1200');
+  expect(record.fee().to_str()).to.eql('0');
+  expect(record.counterparties().size()).to.eql(1);
+  expect(record.counterparties().get(0).as_bytes()).to.eql(other.as_bytes());
+  expect(record.certificate_kind()).to.eql(undefined);
+  expect(history.records().size()).to.eql(1);
+
+  const unrelated = HistoryBuilder.new(AddressDiscrimination.Test);
+  expect(unrelated.add_block(block0).size()).to.eql(0);
+  expect(unrelated.records().size()).to.eql(0);
+});
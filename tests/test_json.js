@@ -0,0 +1,111 @@
+import { expect } from 'chai';
+import { thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+const genesisHash =
+  '6a702a181151b772ca0acbdc4d2870ed80c09b626b29fffc2e47abf2330ad0cd';
+const account = {
+  address: 'ca1qh9u0nxmnfg7af8ycuygx57p5xgzmnmgtaeer9xun7hly6mlgt3pj2xk344',
+  publicKey:
+    'ed25519_pk1e0rueku628h2fex8pzp48sdpjqku76zlwwgefhyl4lexkl6zugvs0uuy0w',
+  privateKey:
+    'ed25519e_sk1gz0ff4w444nwejap5shxrllypz5euswq6wn04fffzes02atw99xkd4jn838v3vrfg9eqt7f4sxjlsy0tdcmj0d2dqvwc8ztwgyfnwyszvjg32'
+};
+const poolId =
+  '541db50349e2bc1a5b1a73939b9d86fc45067117cc930c36afbb6fb0a9329d41';
+
+it('exports and imports a stake delegation as JSON', async () => {
+  const {
+    PoolId,
+    TransactionBuilder,
+    Address,
+    Input,
+    Inputs,
+    Outputs,
+    Value,
+    PublicKey,
+    Certificate,
+    Fragment,
+    PrivateKey,
+    Witness,
+    Witnesses,
+    SpendingCounter,
+    Hash,
+    Account,
+    StakeDelegation,
+    DelegationType,
+    PayloadAuthData,
+    StakeDelegationAuthData,
+    AccountBindingSignature
+  } = await rust;
+
+  const certificate = Certificate.stake_delegation(
+    StakeDelegation.new(
+      DelegationType.full(PoolId.from_hex(poolId)),
+      PublicKey.from_bech32(account.publicKey)
+    )
+  );
+
+  const inputs = Inputs.new();
+  inputs.add(
+    Input.from_account(
+      Account.from_address(Address.from_string(account.address)),
+      Value.from_str('1000')
+    )
+  );
+
+  const builder = new TransactionBuilder()
+    .payload(certificate)
+    .set_ios(inputs, Outputs.new());
+  const witnesses = Witnesses.new();
+  witnesses.add(
+    Witness.for_account(
+      Hash.from_hex(genesisHash),
+      builder.get_auth_data_for_witness(),
+      PrivateKey.from_bech32(account.privateKey),
+      SpendingCounter.zero()
+    )
+  );
+  const builderSetAuth = builder.set_witnesses(witnesses);
+  const transaction = builderSetAuth.set_payload_auth(
+    PayloadAuthData.for_stake_delegation(
+      StakeDelegationAuthData.new(
+        AccountBindingSignature.new_single(
+          PrivateKey.from_bech32(account.privateKey),
+          builderSetAuth.get_auth_data()
+        )
+      )
+    )
+  );
+  const fragment = Fragment.from_transaction(transaction);
+
+  const json = fragment.to_js_value();
+  expect(json.type).to.eql('stake_delegation');
+  expect(json.payload.delegation).to.eql({ type: 'full', pool_id: poolId });
+  expect(json.inputs[0].type).to.eql('account');
+  expect(json.inputs[0].value).to.eql('1000');
+  expect(json.witnesses[0].type).to.eql('account');
+  expect(json.payload_auth.type).to.eql('account_binding');
+
+  const imported = Fragment.from_json(fragment.to_json());
+  expect(imported.as_bytes()).to.eql(fragment.as_bytes());
+  expect(Fragment.from_js_value(json).as_bytes()).to.eql(fragment.as_bytes());
+});
+
+it('rejects a payload that does not match the fragment type', async () => {
+  const { Address, Fragment, ErrorKind } = await rust;
+
+  const json = {
+    type: 'stake_delegation',
+    inputs: [],
+    outputs: [],
+    witnesses: []
+  };
+  expect(thrownBy(() => Fragment.from_js_value(json)).kind()).to.eql(
+    ErrorKind.WrongType
+  );
+
+  const address = Address.from_string(account.address);
+  expect(JSON.parse(address.to_json())).to.eql(account.address);
+});
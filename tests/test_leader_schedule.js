@@ -0,0 +1,60 @@
+import { expect } from 'chai';
+import { thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+it('computes the slots led by a stake pool', async () => {
+  const {
+    BlockDate,
+    ErrorKind,
+    LeaderScheduleCalculator,
+    Value,
+    VrfSecretKey
+  } = await rust;
+
+  const calculator = LeaderScheduleCalculator.new(
+    VrfSecretKey.generate(),
+    1000,
+    10
+  );
+  const nonce = new Uint8Array(32);
+
+  // with all the stake and every slot active, the pool leads the whole epoch
+  const slots = calculator.schedule(
+    2,
+    nonce,
+    Value.from_str('100'),
+    Value.from_str('100')
+  );
+  expect(slots.size()).to.eql(10);
+  expect(slots.get(3).date().epoch()).to.eql(2);
+  expect(slots.get(3).date().slot()).to.eql(3);
+  expect(slots.get(3).proof().length).to.be.above(0);
+
+  const slot = calculator.leads(
+    BlockDate.new(2, 3),
+    nonce,
+    Value.from_str('100'),
+    Value.from_str('100')
+  );
+  expect(slot.date().slot()).to.eql(3);
+  expect(slot.proof().length).to.eql(slots.get(3).proof().length);
+
+  const overstaked = thrownBy(() =>
+    calculator.schedule(2, nonce, Value.from_str('101'), Value.from_str('100'))
+  );
+  expect(overstaked.kind()).to.eql(ErrorKind.InvalidValue);
+
+  const error = thrownBy(() =>
+    calculator.schedule(
+      2,
+      nonce.slice(1),
+      Value.from_str('100'),
+      Value.from_str('100')
+    )
+  );
+  expect(error.kind()).to.eql(ErrorKind.InvalidLength);
+  expect(() =>
+    LeaderScheduleCalculator.new(VrfSecretKey.generate(), 0, 10)
+  ).to.throw();
+});
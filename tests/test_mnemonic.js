@@ -0,0 +1,122 @@
+import { expect } from 'chai';
+
+const rust = import('../pkg/js_chain_libs');
+
+// test vectors from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+const vectors = [
+  {
+    entropy: '00000000000000000000000000000000',
+    phrase:
+      'abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about'
+  },
+  {
+    entropy: '7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f',
+    phrase:
+      'legal winner thank year wave sausage worth useful legal winner thank yellow'
+  },
+  {
+    entropy: '6610b25967cdcca9d59875f5cb50b0ea75433311869e930b',
+    phrase:
+      'gravity machine north sort system female filter attitude volume fold club stay feature office ecology stable narrow fog'
+  },
+  {
+    entropy:
+      '8080808080808080808080808080808080808080808080808080808080808080',
+    phrase:
+      'letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless'
+  }
+];
+
+it('converts entropy to mnemonic and back', async () => {
+  const { Mnemonic, MnemonicLanguage } = await rust;
+
+  vectors.forEach(({ entropy, phrase }) => {
+    const fromEntropy = Mnemonic.from_entropy(
+      hexStringToBytes(entropy),
+      MnemonicLanguage.English
+    );
+    expect(fromEntropy.to_phrase()).to.eql(phrase);
+
+    const fromPhrase = Mnemonic.from_phrase(phrase, MnemonicLanguage.English);
+    expect(fromPhrase.to_entropy()).to.eql(hexStringToBytes(entropy));
+  });
+});
+
+it('rejects invalid checksum', async () => {
+  const { Mnemonic, MnemonicLanguage } = await rust;
+
+  expect(
+    Mnemonic.validate(
+      'abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon',
+      MnemonicLanguage.English
+    )
+  ).to.eql(false);
+});
+
+it('generates mnemonics of every size', async () => {
+  const { Mnemonic, MnemonicLanguage } = await rust;
+
+  [12, 15, 18, 21, 24].forEach(wordCount => {
+    const mnemonic = Mnemonic.generate(wordCount, MnemonicLanguage.Japanese);
+    expect(mnemonic.word_count()).to.eql(wordCount);
+    expect(
+      Mnemonic.validate(mnemonic.to_phrase(), MnemonicLanguage.Japanese)
+    ).to.eql(true);
+  });
+});
+
+// root key test vectors from https://github.com/cardano-foundation/CIPs/blob/master/CIP-0003/Icarus.md
+const rootKeyVectors = [
+  {
+    phrase:
+      'eight country switch draw meat scout mystery blade tip drift useless good keep usage title',
+    password: '',
+    rootKey:
+      'c065afd2832cd8b087c4d9ab7011f481ee1e0721e78ea5dd609f3ab3f156d245d176bd8fd4ec60b4731c3918a2a72a0226c0cd119ec35b47e4d55884667f552a23f7fdcd4a10c6cd2c7393ac61d877873e248f417634aa3d812af327ffe9d620'
+  },
+  {
+    phrase:
+      'eight country switch draw meat scout mystery blade tip drift useless good keep usage title',
+    password: 'foo',
+    rootKey:
+      '70531039904019351e1afb361cd1b312a4d0565d4ff9f8062d38acf4b15cce41d7b5738d9c893feea55512a3004acb0d222c35d3e3d5cde943a15a9824cbac59443cf67e589614076ba01e354b1a432e0e6db3b59e37fc56b5fb0222970a010e'
+  }
+];
+
+it('restores root key from mnemonic', async () => {
+  const { Bip32PrivateKey } = await rust;
+
+  rootKeyVectors.forEach(({ phrase, password, rootKey }) => {
+    const key = Bip32PrivateKey.from_mnemonic(
+      phrase,
+      Uint8Array.from(password, c => c.charCodeAt(0))
+    );
+    expect(key.as_bytes()).to.eql(hexStringToBytes(rootKey));
+  });
+});
+
+function hexStringToBytes(string) {
+  const bytes = [];
+  for (let c = 0; c < string.length; c += 2)
+    bytes.push(parseInt(string.substr(c, 2), 16));
+  return Uint8Array.from(bytes);
+}
+
+it('accepts composed accented words', async () => {
+  const { Mnemonic, MnemonicLanguage } = await rust;
+
+  const entropy = hexStringToBytes('00000000000000000000000000000000');
+  const phrase = Mnemonic.from_entropy(
+    entropy,
+    MnemonicLanguage.Spanish
+  ).to_phrase();
+  const composed = phrase.normalize('NFC');
+  expect(composed).to.not.eql(phrase);
+
+  expect(
+    Mnemonic.from_phrase(composed, MnemonicLanguage.Spanish).to_entropy()
+  ).to.eql(entropy);
+  expect(
+    Mnemonic.from_phrase(phrase, MnemonicLanguage.Spanish).to_entropy()
+  ).to.eql(entropy);
+});
@@ -0,0 +1,46 @@
+import { expect } from 'chai';
+import { thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+it('builds a multisig account witness from partial signatures', async () => {
+  const {
+    MultisigDeclaration,
+    MultisigPartialSignature,
+    MultisigPartialSignatures,
+    Witness,
+    PrivateKey,
+    Hash,
+    TransactionSignDataHash,
+    SpendingCounter,
+    AddressDiscrimination,
+    ErrorKind
+  } = await rust;
+
+  const keys = [0, 1, 2].map(() => PrivateKey.generate_ed25519());
+  const declaration = MultisigDeclaration.new(2);
+  keys.forEach(key => declaration.add_owner(key.to_public()));
+  const address = declaration.to_address(AddressDiscrimination.Test);
+  expect(address.to_multisig_address()).to.not.eql(undefined);
+
+  const genesisHash = Hash.calculate(new Uint8Array([0]));
+  const transactionId = TransactionSignDataHash.from_bytes(new Uint8Array(32));
+  const sign = key =>
+    MultisigPartialSignature.sign(
+      genesisHash,
+      transactionId,
+      SpendingCounter.zero(),
+      key
+    );
+
+  const signatures = MultisigPartialSignatures.new();
+  signatures.add(sign(keys[0]));
+  const error = thrownBy(() =>
+    Witness.for_multisig_account(declaration, signatures)
+  );
+  expect(error.kind()).to.eql(ErrorKind.InvalidArgument);
+
+  signatures.add(MultisigPartialSignature.from_bytes(sign(keys[2]).as_bytes()));
+  const witness = Witness.for_multisig_account(declaration, signatures);
+  expect(witness.to_json()).to.contain('multisig');
+});
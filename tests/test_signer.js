@@ -0,0 +1,180 @@
+import { expect } from 'chai';
+import { mockBlock0Builder } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+it('signs a transaction with an external signer', async () => {
+  const {
+    sign_transaction,
+    TransactionBuilder,
+    InputOutputBuilder,
+    Input,
+    UtxoPointer,
+    FragmentId,
+    Address,
+    AddressDiscrimination,
+    Value,
+    Signer,
+    InputSigner,
+    InputSigners,
+    PayloadSigners,
+    SignatureKind,
+    ResolvedInput,
+    ResolvedInputs,
+    PrivateKey,
+    Hash
+  } = await rust;
+
+  const key = PrivateKey.generate_ed25519();
+  const address = Address.single_from_public_key(
+    key.to_public(),
+    AddressDiscrimination.Test
+  );
+  const iobuilder = InputOutputBuilder.empty();
+  iobuilder.add_input(
+    Input.from_utxo(
+      UtxoPointer.new(
+        FragmentId.calculate(new Uint8Array([0])),
+        0,
+        Value.from_str('32')
+      )
+    )
+  );
+  iobuilder.add_output(address, Value.from_str('20'));
+  const ios = iobuilder.build();
+  const builder = new TransactionBuilder()
+    .no_payload()
+    .set_ios(ios.inputs(), ios.outputs());
+
+  const kinds = [];
+  const external = Signer.from_js({
+    sign: async (kind, data) => {
+      kinds.push(kind);
+      return key.sign(data).as_bytes();
+    }
+  });
+  const signers = InputSigners.new();
+  signers.add(InputSigner.utxo(external));
+
+  const genesisHash = Hash.calculate(new Uint8Array([0]));
+  const transaction = await sign_transaction(
+    builder,
+    Hash.calculate(new Uint8Array([0])),
+    signers,
+    PayloadSigners.new()
+  );
+  expect(kinds).to.eql([SignatureKind.Utxo]);
+
+  const resolved = ResolvedInputs.new();
+  resolved.add(ResolvedInput.utxo(address));
+  expect(
+    transaction.verify_witnesses(genesisHash, resolved).is_valid()
+  ).to.eql(true);
+});
+
+it('signs a pool certificate with any quorum of the owners', async () => {
+  const {
+    Address,
+    AddressDiscrimination,
+    Certificate,
+    Fee,
+    FragmentVerdictKind,
+    GenesisPraosLeader,
+    KesPublicKey,
+    LedgerSimulator,
+    NetworkSettings,
+    Output,
+    OutputPolicy,
+    PayloadSigner,
+    PayloadSigners,
+    PoolRegistration,
+    PrivateKey,
+    PublicKeys,
+    Signer,
+    SimpleTransactionBuilder,
+    TaxType,
+    TimeOffsetSeconds,
+    U128,
+    UtxoPointer,
+    Value,
+    VrfPublicKey
+  } = await rust;
+
+  const key = PrivateKey.generate_ed25519();
+  const address = Address.single_from_public_key(
+    key.to_public(),
+    AddressDiscrimination.Test
+  );
+  const builder = await mockBlock0Builder();
+  builder.set_fee(
+    Fee.linear_fee(Value.from_str('10'), Value.from_str('0'), Value.from_str('0'))
+  );
+  builder.add_initial_fund(Output.new(address, Value.from_str('1000')));
+  const block0 = builder.build();
+  const fund = block0
+    .fragments()
+    .get(1)
+    .id();
+
+  const ownerKeys = [0, 1, 2].map(() => PrivateKey.generate_ed25519());
+  const owners = new PublicKeys();
+  ownerKeys.forEach(owner => owners.add(owner.to_public()));
+  const registration = new PoolRegistration(
+    U128.from_str('1'),
+    owners,
+    new PublicKeys(),
+    2,
+    TimeOffsetSeconds.from_string('0'),
+    GenesisPraosLeader.new(
+      KesPublicKey.from_bech32(
+        'kes25519-12-pk1s6pr56t6uzkmgdqs2krrh0tw9yyvdwfkzk7e90nsfxg08wqxg6qs9nuu5s'
+      ),
+      VrfPublicKey.from_bech32(
+        'vrf_pk1fz79zuzm2k8agqs7a5fgdpzprgpnzh58jhz35wjftd0km8dagfdqs8e08k'
+      )
+    ),
+    TaxType.zero(),
+    undefined
+  );
+
+  // the second owner doesn't sign, the signers are not a prefix of the owners
+  const signers = PayloadSigners.new();
+  signers.add(
+    PayloadSigner.pool_owner(Signer.from_private_key(ownerKeys[2]), 2)
+  );
+  signers.add(
+    PayloadSigner.pool_owner(Signer.from_private_key(ownerKeys[0]), 0)
+  );
+  const transaction = SimpleTransactionBuilder.new(
+    NetworkSettings.from_block0(block0)
+  );
+  transaction.add_utxo_input(
+    UtxoPointer.new(fund, 0, Value.from_str('1000')),
+    key
+  );
+  transaction.set_certificate(
+    Certificate.stake_pool_registration(registration),
+    signers
+  );
+  const fragment = transaction.finalize(OutputPolicy.one(address));
+
+  const simulator = LedgerSimulator.new(block0);
+  expect(simulator.check_fragment(fragment).kind()).to.eql(
+    FragmentVerdictKind.Valid
+  );
+
+  const unindexed = PayloadSigners.new();
+  unindexed.add(PayloadSigner.account(Signer.from_private_key(ownerKeys[0])));
+  const rejected = SimpleTransactionBuilder.new(
+    NetworkSettings.from_block0(block0)
+  );
+  rejected.add_utxo_input(
+    UtxoPointer.new(fund, 0, Value.from_str('1000')),
+    key
+  );
+  rejected.set_certificate(
+    Certificate.stake_pool_registration(registration),
+    unindexed
+  );
+  expect(() => rejected.finalize(OutputPolicy.one(address))).to.throw();
+});
@@ -0,0 +1,66 @@
+import { expect } from 'chai';
+import { thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+it('generates the keys of a stake pool leader', async () => {
+  const {
+    ErrorKind,
+    GenesisPraosLeader,
+    KesPublicKey,
+    KesSecretKey,
+    VrfPublicKey,
+    VrfSecretKey
+  } = await rust;
+
+  const secrets = GenesisPraosLeader.generate();
+  const kes = secrets.kes_secret_key();
+  const vrf = secrets.vrf_secret_key();
+
+  expect(KesSecretKey.from_bech32(kes.to_bech32()).as_bytes()).to.eql(
+    kes.as_bytes()
+  );
+  expect(KesSecretKey.from_bytes(kes.as_bytes()).to_bech32()).to.eql(
+    kes.to_bech32()
+  );
+  expect(VrfSecretKey.from_bech32(vrf.to_bech32()).as_bytes()).to.eql(
+    vrf.as_bytes()
+  );
+  expect(VrfSecretKey.from_bytes(vrf.as_bytes()).to_bech32()).to.eql(
+    vrf.to_bech32()
+  );
+
+  const leader = secrets.leader();
+  expect(leader.kes_public_key().to_bech32()).to.eql(
+    kes.to_public().to_bech32()
+  );
+  expect(leader.vrf_public_key().to_bech32()).to.eql(
+    vrf.to_public().to_bech32()
+  );
+  expect(
+    KesPublicKey.from_bytes(kes.to_public().as_bytes()).to_bech32()
+  ).to.eql(kes.to_public().to_bech32());
+  expect(
+    VrfPublicKey.from_bytes(vrf.to_public().as_bytes()).to_bech32()
+  ).to.eql(vrf.to_public().to_bech32());
+
+  const other = KesSecretKey.generate();
+  expect(other.to_bech32()).to.not.eql(kes.to_bech32());
+  expect(VrfSecretKey.generate().to_bech32()).to.not.eql(vrf.to_bech32());
+
+  expect(kes.period()).to.eql(0);
+  kes.update(3);
+  expect(kes.period()).to.eql(3);
+  // the public key doesn't change when the key evolves
+  expect(kes.to_public().to_bech32()).to.eql(
+    leader.kes_public_key().to_bech32()
+  );
+  expect(thrownBy(() => kes.update(2)).kind()).to.eql(
+    ErrorKind.InvalidArgument
+  );
+  expect(thrownBy(() => kes.update(1 << 12)).kind()).to.eql(
+    ErrorKind.InvalidArgument
+  );
+  const error = thrownBy(() => KesSecretKey.from_bytes(new Uint8Array(3)));
+  expect(error.kind()).to.eql(ErrorKind.InvalidKey);
+});
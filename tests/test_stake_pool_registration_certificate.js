@@ -1,4 +1,5 @@
 import { expect } from 'chai';
+import { thrownBy } from './helpers';
 
 const rust = import('../pkg/js_chain_libs');
 
@@ -12,7 +13,9 @@ it('generates certificate', async () => {
     KesPublicKey,
     VrfPublicKey,
     PublicKeys,
-    TimeOffsetSeconds
+    TimeOffsetSeconds,
+    GenesisPraosLeader,
+    TaxType
   } = await rust;
   const serial = U128.from_str('1010101010');
   const owners = new PublicKeys();
@@ -39,11 +42,78 @@ it('generates certificate', async () => {
     operators,
     managementThreshold,
     startValidity,
-    kesPublicKey,
-    vrfPublicKey
+    GenesisPraosLeader.new(kesPublicKey, vrfPublicKey),
+    TaxType.zero(),
+    undefined
   );
 
   expect(poolRegistration.id().to_string()).to.eql(
     '8a7b798c09f4ee16f1348d71a9b534a429f0cec9d6ef5ad9e274b2917167501c'
   );
 });
+
+it('configures the pool rewards and permissions', async () => {
+  const {
+    PoolRegistration,
+    U128,
+    PublicKey,
+    KesPublicKey,
+    VrfPublicKey,
+    PublicKeys,
+    TimeOffsetSeconds,
+    GenesisPraosLeader,
+    TaxType,
+    Value,
+    Account,
+    ErrorKind
+  } = await rust;
+  const owner = PublicKey.from_bech32(
+    'ed25519_pk1m2mmtf6320yl3z9h2x23hhxtawwcudkpgzngp25wh2rpffdj8gmqws8lgr'
+  );
+  const owners = new PublicKeys();
+  owners.add(owner);
+  const leader = GenesisPraosLeader.new(
+    KesPublicKey.from_bech32(
+      'kes25519-12-pk1s6pr56t6uzkmgdqs2krrh0tw9yyvdwfkzk7e90nsfxg08wqxg6qs9nuu5s'
+    ),
+    VrfPublicKey.from_bech32(
+      'vrf_pk1fz79zuzm2k8agqs7a5fgdpzprgpnzh58jhz35wjftd0km8dagfdqs8e08k'
+    )
+  );
+  const tax = TaxType.new(
+    Value.from_str('100'),
+    Value.from_str('1'),
+    Value.from_str('10'),
+    Value.from_str('1000')
+  );
+
+  const registration = new PoolRegistration(
+    U128.from_str('1'),
+    owners,
+    new PublicKeys(),
+    1,
+    TimeOffsetSeconds.from_string('0'),
+    leader,
+    tax,
+    Account.single_from_public_key(owner)
+  );
+  expect(registration.rewards().ratio_denominator().to_str()).to.eql('10');
+  expect(registration.rewards().max_limit().to_str()).to.eql('1000');
+  expect(registration.reward_account()).to.not.eql(undefined);
+  expect(registration.management_threshold()).to.eql(1);
+
+  const thresholdError = thrownBy(() =>
+    registration.set_management_threshold(2)
+  );
+  expect(thresholdError.kind()).to.eql(ErrorKind.InvalidArgument);
+
+  const ratioError = thrownBy(() =>
+    TaxType.new(
+      Value.from_str('0'),
+      Value.from_str('1'),
+      Value.from_str('0'),
+      undefined
+    )
+  );
+  expect(ratioError.kind()).to.eql(ErrorKind.InvalidValue);
+});
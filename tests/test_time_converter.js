@@ -0,0 +1,38 @@
+import { expect } from 'chai';
+
+const rust = import('../pkg/js_chain_libs');
+
+const block0Date = 1576000000;
+
+it('converts block dates to time and back', async () => {
+  const { BlockDate, TimeConverter } = await rust;
+
+  // slots of 2 seconds, 100 slots per epoch
+  const converter = TimeConverter.new(block0Date, 2, 100);
+
+  expect(converter.to_time(BlockDate.new(0, 0))).to.eql(block0Date * 1000);
+  expect(converter.to_time(BlockDate.new(1, 5))).to.eql(
+    (block0Date + 105 * 2) * 1000
+  );
+  expect(() => converter.to_time(BlockDate.new(0, 100))).to.throw();
+
+  const date = converter.block_date_at((block0Date + 105 * 2) * 1000 + 1500);
+  expect(date.epoch()).to.eql(1);
+  expect(date.slot()).to.eql(5);
+
+  expect(() => converter.block_date_at((block0Date - 1) * 1000)).to.throw();
+});
+
+it('converts time offsets', async () => {
+  const { TimeConverter, TimeOffsetSeconds } = await rust;
+
+  const converter = TimeConverter.new(block0Date, 2, 100);
+
+  const offset = TimeOffsetSeconds.from_string('3600');
+  expect(converter.time_offset_to_time(offset)).to.eql(
+    (block0Date + 3600) * 1000
+  );
+  expect(
+    converter.time_to_time_offset((block0Date + 3600) * 1000 + 999).to_string()
+  ).to.eql('3600');
+});
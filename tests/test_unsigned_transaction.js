@@ -0,0 +1,77 @@
+import { expect } from 'chai';
+import { thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+const genesisHash =
+  '6a702a181151b772ca0acbdc4d2870ed80c09b626b29fffc2e47abf2330ad0cd';
+const inputAccount = {
+  address: 'ca1qh9u0nxmnfg7af8ycuygx57p5xgzmnmgtaeer9xun7hly6mlgt3pj2xk344',
+  privateKey:
+    'ed25519e_sk1gz0ff4w444nwejap5shxrllypz5euswq6wn04fffzes02atw99xkd4jn838v3vrfg9eqt7f4sxjlsy0tdcmj0d2dqvwc8ztwgyfnwyszvjg32'
+};
+const outputAddress =
+  'ca1q5nr5pvt9e5p009strshxndrsx5etcentslp2rwj6csm8sfk24a2w3swacn';
+
+it('signs an unsigned transaction on another machine', async () => {
+  const {
+    UnsignedTransaction,
+    SigningRequirementKind,
+    Payload,
+    Inputs,
+    Outputs,
+    Input,
+    Output,
+    Account,
+    Address,
+    Value,
+    Witness,
+    Hash,
+    PrivateKey,
+    SpendingCounter,
+    PayloadAuthData,
+    ErrorKind
+  } = await rust;
+
+  const accountAddress = Address.from_string(inputAccount.address);
+  const inputs = Inputs.new();
+  inputs.add(
+    Input.from_account(Account.from_address(accountAddress), Value.from_str('100'))
+  );
+  const outputs = Outputs.new();
+  outputs.add(Output.new(Address.from_string(outputAddress), Value.from_str('90')));
+
+  const unsigned = UnsignedTransaction.new(Payload.no_payload(), inputs, outputs);
+  const encoded = unsigned.to_bech32();
+  expect(encoded.startsWith('unsigned_tx1')).to.eql(true);
+
+  // air-gapped signer
+  const received = UnsignedTransaction.from_bech32(encoded);
+  const requirement = received.requirement(0);
+  expect(requirement.kind()).to.eql(SigningRequirementKind.Account);
+  expect(requirement.account().to_hex()).to.eql(
+    Account.from_address(accountAddress)
+      .to_identifier()
+      .to_hex()
+  );
+  received.add_witness(
+    0,
+    Witness.for_account(
+      Hash.from_hex(genesisHash),
+      received.id(),
+      PrivateKey.from_bech32(inputAccount.privateKey),
+      SpendingCounter.zero()
+    )
+  );
+  const signed = received.as_bytes();
+
+  const back = UnsignedTransaction.from_bytes(signed);
+  expect(back.is_complete()).to.eql(true);
+  const transaction = back.finalize(PayloadAuthData.for_no_payload());
+  expect(transaction.outputs().get(0).value().to_str()).to.eql('90');
+
+  const error = thrownBy(() =>
+    unsigned.finalize(PayloadAuthData.for_no_payload())
+  );
+  expect(error.kind()).to.eql(ErrorKind.TransactionBuilder);
+});
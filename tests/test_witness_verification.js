@@ -0,0 +1,62 @@
+import { expect } from 'chai';
+import { thrownBy } from './helpers';
+
+const rust = import('../pkg/js_chain_libs');
+
+it('verifies utxo and multisig witnesses', async () => {
+  const {
+    MultisigDeclaration,
+    MultisigPartialSignature,
+    MultisigPartialSignatures,
+    Witness,
+    PrivateKey,
+    Address,
+    AddressDiscrimination,
+    Hash,
+    TransactionSignDataHash,
+    SpendingCounter,
+    ErrorKind
+  } = await rust;
+
+  const genesisHash = Hash.calculate(new Uint8Array([0]));
+  const transactionId = TransactionSignDataHash.from_bytes(new Uint8Array(32));
+  const counter = SpendingCounter.zero();
+
+  const key = PrivateKey.generate_ed25519();
+  const address = Address.single_from_public_key(
+    key.to_public(),
+    AddressDiscrimination.Test
+  );
+  const witness = Witness.for_utxo(genesisHash, transactionId, key);
+  expect(witness.verify(genesisHash, transactionId, address, counter)).to.eql(
+    true
+  );
+  const other = PrivateKey.generate_ed25519().to_public();
+  expect(
+    witness.verify_with_key(genesisHash, transactionId, other, counter)
+  ).to.eql(false);
+
+  const keys = [0, 1].map(() => PrivateKey.generate_ed25519());
+  const declaration = MultisigDeclaration.new(2);
+  keys.forEach(owner => declaration.add_owner(owner.to_public()));
+  const signatures = MultisigPartialSignatures.new();
+  keys.forEach(owner =>
+    signatures.add(
+      MultisigPartialSignature.sign(genesisHash, transactionId, counter, owner)
+    )
+  );
+  const multisig = Witness.for_multisig_account(declaration, signatures);
+  expect(
+    multisig.verify_multisig(genesisHash, transactionId, declaration, counter)
+  ).to.eql(true);
+
+  const error = thrownBy(() =>
+    multisig.verify(
+      genesisHash,
+      transactionId,
+      declaration.to_address(AddressDiscrimination.Test),
+      counter
+    )
+  );
+  expect(error.kind()).to.eql(ErrorKind.WrongType);
+});